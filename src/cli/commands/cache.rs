@@ -0,0 +1,125 @@
+//! `ax cache` Command
+//!
+//! Lists, cleans and verifies the registry content cache in ~/.ax/cache.
+
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::cli::CacheAction;
+use crate::core::cache::{Cache, CacheEntry};
+use crate::say;
+use crate::utils::{output, paths, ui};
+
+/// Execute the cache command
+pub async fn execute(action: CacheAction) -> Result<()> {
+    let cache = Cache::open()?;
+    match action {
+        CacheAction::Ls => list(&cache),
+        CacheAction::Clean => clean(&cache),
+        CacheAction::Verify => verify(&cache),
+    }
+}
+
+fn list(cache: &Cache) -> Result<()> {
+    let entries = cache.entries()?;
+    let dir = paths::ax_cache_dir()?;
+    if output::is_json() {
+        return output::emit(&serde_json::json!({ "dir": dir, "entries": entries }));
+    }
+
+    ui::print_header(&format!("Cache ({})", dir.display()));
+    if entries.is_empty() {
+        say!("  {} The cache is empty.", "!".yellow().bold());
+        return Ok(());
+    }
+
+    say!(
+        "  {:<10} {:<10} {}",
+        "SIZE".bold().cyan(),
+        "FETCHED".bold().cyan(),
+        "URL".bold().cyan()
+    );
+    for entry in &entries {
+        say!(
+            "  {:<10} {:<10} {}",
+            format_size(entry.size),
+            format_age(entry).dimmed(),
+            entry.url
+        );
+    }
+
+    let total: u64 = entries.iter().map(|entry| entry.size).sum();
+    say!();
+    say!(
+        "  {} {} document(s), {}",
+        "→".cyan(),
+        entries.len().to_string().bold(),
+        format_size(total)
+    );
+    Ok(())
+}
+
+fn clean(cache: &Cache) -> Result<()> {
+    let removed = cache.clean()?;
+    if output::is_json() {
+        return output::emit(&serde_json::json!({ "removed": removed }));
+    }
+
+    say!();
+    ui::print_success(&format!("Removed {} cached document(s)", removed));
+    Ok(())
+}
+
+fn verify(cache: &Cache) -> Result<()> {
+    let checked = cache.entries()?.len();
+    let broken = cache.verify()?;
+    for entry in &broken {
+        cache.remove(&entry.url)?;
+    }
+
+    if output::is_json() {
+        let removed: Vec<&str> = broken.iter().map(|entry| entry.url.as_str()).collect();
+        return output::emit(&serde_json::json!({ "checked": checked, "removed": removed }));
+    }
+
+    say!();
+    for entry in &broken {
+        ui::print_warning(&format!("Corrupted, removed: {}", entry.url));
+    }
+    if broken.is_empty() {
+        ui::print_success(&format!("All {} cached document(s) verified", checked));
+    } else {
+        say!(
+            "\n  {} {} of {} document(s) will be fetched again on next use",
+            "→".cyan(),
+            broken.len(),
+            checked
+        );
+    }
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+fn format_age(entry: &CacheEntry) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let age = now.saturating_sub(entry.fetched_at);
+
+    match age {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", age / 60),
+        3600..=86399 => format!("{}h ago", age / 3600),
+        _ => format!("{}d ago", age / 86400),
+    }
+}
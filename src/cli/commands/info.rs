@@ -0,0 +1,508 @@
+//! `ax info` Command
+//!
+//! Shows an agent's details before installing it: versions, identity,
+//! skills, MCP servers and the environment variables they need, declared
+//! dependencies, and what each target does with them.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::agent::{AgentConfig, Requirement};
+use crate::core::error::AxError;
+use crate::core::registry::Registry;
+use crate::core::{schema, secrets};
+use crate::installers::{get_installer, get_installer_in, Target};
+use crate::say;
+use crate::utils::paths::BaseDirs;
+use crate::utils::{output, ui};
+
+use super::install;
+
+/// Everything `ax info` reports, for `--output json`
+#[derive(Serialize)]
+struct InfoReport {
+    name: String,
+    version: String,
+    /// Newest published version
+    latest: String,
+    versions: Vec<String>,
+    description: String,
+    author: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extends: Option<String>,
+    skills: Vec<SkillInfo>,
+    mcp: Vec<McpInfo>,
+    requires: Vec<String>,
+    targets: Vec<TargetInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rendered: Option<Vec<RenderedFile>>,
+}
+
+#[derive(Serialize)]
+struct SkillInfo {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    allowed_tools: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct McpInfo {
+    name: String,
+    transport: String,
+    /// The command line (stdio) or server URL (http, sse)
+    endpoint: String,
+    env_required: Vec<String>,
+}
+
+/// What a target installs from this agent
+#[derive(Serialize)]
+struct TargetInfo {
+    target: String,
+    identity: bool,
+    skills: usize,
+    mcp: usize,
+    /// MCP tools left out because the target can't configure their transport
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    unsupported_mcp: Vec<String>,
+    /// Whether agent.yaml has a `targets:` override for this target
+    overrides: bool,
+}
+
+/// A file an installer would write
+#[derive(Serialize)]
+struct RenderedFile {
+    path: String,
+    content: String,
+}
+
+/// Execute the info command
+pub async fn execute(agent_spec: &str, render_target: Option<Target>) -> Result<()> {
+    let requirement: Requirement = agent_spec.parse()?;
+
+    let spinner = ui::create_spinner(&format!("Fetching {}...", requirement));
+    let registry = Registry::new();
+    let mut agent = registry.fetch_agent(&requirement.name).await?;
+    let latest = agent.version.clone();
+    // The index is only needed for version history, so it may be missing
+    let versions = registry
+        .fetch_agents()
+        .await
+        .ok()
+        .and_then(|index| index.into_iter().find(|entry| entry.name == agent.name))
+        .map(|entry| entry.versions)
+        .unwrap_or_default();
+
+    // An older version is fetched when the latest doesn't match the request
+    if !requirement.matches(&agent.version) {
+        let Some(version) = newest_match(&requirement, &versions) else {
+            spinner.finish_and_clear();
+            return Err(anyhow::Error::from(AxError::NotFound(requirement.to_string())).context(
+                format!("The registry has {} v{}", agent.name, agent.version),
+            ));
+        };
+        agent = registry.fetch_agent_version(&requirement.name, &version).await?;
+    }
+    spinner.finish_and_clear();
+
+    let mut report = report(&agent, versions, latest);
+    if output::is_json() {
+        if let Some(target) = render_target {
+            report.rendered = Some(render(&agent, target)?);
+        }
+        return output::emit(&report);
+    }
+
+    // Installers may print notes while rendering, so the report goes first
+    print_report(&report);
+    if let Some(target) = render_target {
+        print_rendered(target, &render(&agent, target)?);
+    }
+    Ok(())
+}
+
+/// The newest published version satisfying a requirement
+fn newest_match(requirement: &Requirement, versions: &[String]) -> Option<String> {
+    versions
+        .iter()
+        .filter(|version| requirement.matches(version))
+        .max_by_key(|version| semver::Version::parse(version).ok())
+        .cloned()
+}
+
+fn report(agent: &AgentConfig, mut versions: Vec<String>, latest: String) -> InfoReport {
+    if versions.is_empty() {
+        versions.push(latest.clone());
+    }
+
+    let skills = agent
+        .skills
+        .iter()
+        .map(|skill| SkillInfo {
+            name: skill.name.clone(),
+            description: skill.description.clone(),
+            allowed_tools: schema::split_tools(
+                skill.allowed_tools.as_deref().unwrap_or_default(),
+                char::is_whitespace,
+            )
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        })
+        .collect();
+
+    let mcp = agent
+        .mcp
+        .iter()
+        .map(|tool| {
+            let endpoint = if tool.url.is_some() {
+                tool.remote_url().to_string()
+            } else {
+                std::iter::once(tool.command.as_str())
+                    .chain(tool.args.iter().map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            let env_required: BTreeSet<String> = install::secret_fields(tool)
+                .flat_map(|value| secrets::placeholders(value))
+                .map(str::to_string)
+                .collect();
+            McpInfo {
+                name: tool.name.clone(),
+                transport: tool.transport.to_string(),
+                endpoint,
+                env_required: env_required.into_iter().collect(),
+            }
+        })
+        .collect();
+
+    let targets = Target::ALL
+        .iter()
+        .map(|target| target_info(agent, *target))
+        .collect();
+
+    InfoReport {
+        name: agent.name.clone(),
+        version: agent.version.clone(),
+        latest,
+        versions,
+        description: agent.description.clone(),
+        author: agent.author.clone(),
+        tags: agent.tags.clone(),
+        model: agent.identity.model.clone(),
+        icon: agent.identity.icon.clone(),
+        extends: agent.extends.as_ref().map(ToString::to_string),
+        skills,
+        mcp,
+        requires: agent.requires.iter().map(ToString::to_string).collect(),
+        targets,
+        rendered: None,
+    }
+}
+
+fn target_info(agent: &AgentConfig, target: Target) -> TargetInfo {
+    let installer = get_installer(target, false);
+    let overrides = agent.targets.get(target.id());
+    let selected = |names: Option<&Vec<String>>, name: &str| names.is_none_or(|names| names.iter().any(|n| n == name));
+
+    let skills = agent
+        .skills
+        .iter()
+        .filter(|skill| selected(overrides.and_then(|o| o.skills.as_ref()), &skill.name))
+        .count();
+    let (mcp, unsupported): (Vec<_>, Vec<_>) = agent
+        .mcp
+        .iter()
+        .filter(|tool| selected(overrides.and_then(|o| o.mcp.as_ref()), &tool.name))
+        .partition(|tool| installer.supports_transport(tool.transport));
+
+    TargetInfo {
+        target: target.id().to_string(),
+        identity: installer.installs_identity(),
+        skills: if installer.installs_skills() { skills } else { 0 },
+        mcp: mcp.len(),
+        unsupported_mcp: unsupported.iter().map(|tool| tool.name.clone()).collect(),
+        overrides: overrides.is_some(),
+    }
+}
+
+fn print_report(report: &InfoReport) {
+    let icon = report.icon.as_deref().unwrap_or("🤖");
+    ui::print_header(&format!("{} {} v{}", icon, report.name, report.version));
+    say!("  {}", report.description);
+    say!();
+
+    ui::print_kv("Author", &report.author);
+    ui::print_kv("Model", report.model.as_deref().unwrap_or("(editor default)"));
+    if !report.tags.is_empty() {
+        ui::print_kv("Tags", &report.tags.join(", "));
+    }
+    if let Some(parent) = &report.extends {
+        ui::print_kv("Extends", parent);
+    }
+    let versions: Vec<String> = report
+        .versions
+        .iter()
+        .map(|version| {
+            if *version == report.latest {
+                format!("{} (latest)", version)
+            } else if *version == report.version {
+                format!("{} (shown)", version)
+            } else {
+                version.clone()
+            }
+        })
+        .collect();
+    ui::print_kv("Versions", &versions.join(", "));
+
+    if !report.skills.is_empty() {
+        say!("\n  {}", "Skills".bold());
+        for skill in &report.skills {
+            match &skill.description {
+                Some(description) => ui::print_bullet(&format!("{} — {}", skill.name.green(), description)),
+                None => ui::print_bullet(&skill.name.green().to_string()),
+            }
+            if !skill.allowed_tools.is_empty() {
+                say!("      {} {}", "allowed tools:".dimmed(), skill.allowed_tools.join(", "));
+            }
+        }
+    }
+
+    if !report.mcp.is_empty() {
+        say!("\n  {}", "MCP Servers".bold());
+        for tool in &report.mcp {
+            ui::print_bullet(&format!("{} ({}) {}", tool.name.green(), tool.transport, tool.endpoint.dimmed()));
+            if !tool.env_required.is_empty() {
+                say!("      {} {}", "requires:".dimmed(), tool.env_required.join(", ").yellow());
+            }
+        }
+    }
+
+    if !report.requires.is_empty() {
+        say!("\n  {}", "Dependencies".bold());
+        for requirement in &report.requires {
+            ui::print_bullet(requirement);
+        }
+    }
+
+    say!("\n  {}", "Targets".bold());
+    for target in &report.targets {
+        let mark = |supported: bool| if supported { "✓".green() } else { "✗".red() };
+        let mut parts = vec![format!("{} identity", mark(target.identity))];
+        if !report.skills.is_empty() {
+            parts.push(format!("{} {} skill(s)", mark(target.skills > 0), target.skills));
+        }
+        if !report.mcp.is_empty() {
+            parts.push(format!("{} {} MCP server(s)", mark(target.mcp > 0), target.mcp));
+        }
+
+        let mut notes = Vec::new();
+        if !target.unsupported_mcp.is_empty() {
+            notes.push(format!("unsupported transport: {}", target.unsupported_mcp.join(", ")));
+        }
+        if target.overrides {
+            notes.push("has targets: override".to_string());
+        }
+        let notes = if notes.is_empty() {
+            String::new()
+        } else {
+            format!("  ({})", notes.join("; ")).dimmed().to_string()
+        };
+
+        say!("    {:<10} {}{}", target.target, parts.join("  "), notes);
+    }
+
+    say!();
+    say!(
+        "  {} Install with: {}",
+        "→".cyan(),
+        format!("ax install {}", report.name).cyan().bold()
+    );
+}
+
+fn print_rendered(target: Target, files: &[RenderedFile]) {
+    ui::print_header(&format!("Rendered for {}", target.display_name()));
+    if files.is_empty() {
+        say!("  {} Nothing is written for this target.", "!".yellow().bold());
+    }
+    for file in files {
+        say!("  {} {}", "──".dimmed(), file.path.bold());
+        say!("{}", file.content.trim_end());
+        say!();
+    }
+}
+
+/// Run a target's installer in a scratch directory and collect what it writes
+///
+/// The installer resolves its home and project paths inside the scratch
+/// directory, so neither the project nor the user's editor config is
+/// touched. `${VAR}` secrets are left as placeholders.
+fn render(agent: &AgentConfig, target: Target) -> Result<Vec<RenderedFile>> {
+    let scratch = tempfile::tempdir()?;
+    render_in(agent, target, scratch.path()).with_context(|| AxError::Installer(target.display_name().to_string()))
+}
+
+fn render_in(agent: &AgentConfig, target: Target, scratch: &Path) -> Result<Vec<RenderedFile>> {
+    let home = scratch.join("home");
+    let project = scratch.join("project");
+    fs::create_dir_all(&home)?;
+    fs::create_dir_all(&project)?;
+
+    let base = BaseDirs::new(&home, &project);
+    let installer = get_installer_in(target, false, base.clone());
+    let variables = install::load_variables()?;
+    let (mut agent, _) =
+        install::prepare_for_target(agent, target.id(), installer.as_ref(), &base, &variables, &BTreeMap::new())?;
+    // Tools the target can't configure are listed as unsupported instead
+    agent.mcp.retain(|tool| installer.supports_transport(tool.transport));
+
+    // Remote skill files are downloaded with a blocking client
+    tokio::task::block_in_place(|| -> Result<()> {
+        if !agent.skill_only {
+            installer.install_identity(&agent)?;
+        }
+        installer.install_skills(&agent)?;
+        installer.install_tools(&agent)
+    })?;
+
+    let mut written = Vec::new();
+    collect_files(&project, &mut written)?;
+    collect_files(&home, &mut written)?;
+
+    let mut files = Vec::new();
+    for path in written {
+        let content = fs::read_to_string(&path)
+            .map(|content| unscratch(&content, &home, &project))
+            .unwrap_or_else(|_| "(binary file)".to_string());
+        files.push(RenderedFile {
+            path: display_path(&path, &home, &project),
+            content,
+        });
+    }
+    Ok(files)
+}
+
+/// Every file under `dir`, sorted by path
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Where a scratch file would go for a real install
+fn display_path(path: &Path, home: &Path, project: &Path) -> String {
+    if let Ok(rest) = path.strip_prefix(home) {
+        format!("~/{}", rest.display())
+    } else if let Ok(rest) = path.strip_prefix(project) {
+        format!("./{}", rest.display())
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// Replace scratch paths that installers wrote into file contents
+fn unscratch(content: &str, home: &Path, project: &Path) -> String {
+    content
+        .replace(&format!("{}/", project.display()), "")
+        .replace(&project.display().to_string(), ".")
+        .replace(&home.display().to_string(), "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent() -> AgentConfig {
+        serde_yaml::from_str(
+            r#"
+name: demo
+version: 1.0.0
+description: Demo agent
+author: a
+identity:
+  system_prompt: Be helpful
+skills:
+  - name: lint
+    content: Run the linter
+mcp:
+  - name: search
+    command: npx
+    args: ["search-server"]
+  - name: events
+    transport: sse
+    url: https://mcp.example.com/sse
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_target_support_table() {
+        let agent = agent();
+
+        let cursor = target_info(&agent, Target::Cursor);
+        assert!(cursor.identity);
+        assert_eq!((cursor.skills, cursor.mcp), (1, 2));
+        assert!(cursor.unsupported_mcp.is_empty());
+
+        // Codex has no identity and can't configure SSE servers
+        let codex = target_info(&agent, Target::Codex);
+        assert!(!codex.identity);
+        assert_eq!((codex.skills, codex.mcp), (1, 1));
+        assert_eq!(codex.unsupported_mcp, ["events"]);
+
+        let zed = target_info(&agent, Target::Zed);
+        assert!(zed.identity);
+        assert_eq!(zed.unsupported_mcp, ["events"]);
+    }
+
+    #[test]
+    fn test_render_in_scratch_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = render_in(&agent(), Target::Cursor, dir.path()).unwrap();
+
+        let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "./.cursor/mcp.json",
+                "./.cursor/rules/demo-identity.mdc",
+                "./.cursor/rules/demo-lint.mdc",
+            ]
+        );
+        assert!(files[1].content.contains("Be helpful"));
+        assert!(files[0].content.contains("search-server"));
+        assert!(!files.iter().any(|file| file.content.contains(&dir.path().display().to_string())));
+
+        // Global paths are shown relative to the home directory
+        let files = render_in(&agent(), Target::Windsurf, dir.path()).unwrap();
+        assert!(files.iter().any(|file| file.path == "~/.codeium/windsurf/mcp_config.json"));
+
+        // SSE tools are left out for targets that can't configure them
+        let files = render_in(&agent(), Target::Codex, dir.path()).unwrap();
+        let config = files.iter().find(|file| file.path.ends_with("config.toml")).unwrap();
+        assert!(config.content.contains("[mcp_servers.search]"));
+        assert!(!config.content.contains("events"));
+    }
+}
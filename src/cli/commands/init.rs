@@ -4,90 +4,134 @@
 
 use anyhow::Result;
 use colored::Colorize;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 
 use crate::core::config::ApmConfig;
-use crate::utils::paths;
-use crate::utils::ui;
+use crate::installers::{discover_plugins, Target};
+use crate::say;
+use crate::utils::{detect, output, paths, ui};
+
+/// An editor or plugin in the `--output json` report
+#[derive(Debug, Serialize)]
+struct EditorStatus {
+    id: String,
+    name: String,
+    detected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<PathBuf>,
+}
 
 /// Execute the init command
 pub async fn execute() -> Result<()> {
     ui::print_header("AX Initialization");
 
     // Detect installed editors
-    println!("{} Detecting installed editors...\n", "→".cyan());
-
-    let claude_installed = detect_claude();
-    let cursor_installed = detect_cursor();
-    let vscode_installed = detect_vscode();
+    say!("{} Detecting installed editors...\n", "→".cyan());
+
+    let mut detected = Vec::new();
+    let mut editors = Vec::new();
+    for target in Target::ALL {
+        let installed = detect::is_installed(target);
+        let path = detect::config_dir(target);
+        print_editor_status(target.display_name(), installed, path.clone());
+        if installed {
+            detected.push(target);
+        }
+        editors.push(EditorStatus {
+            id: target.id().to_string(),
+            name: target.display_name().to_string(),
+            detected: installed,
+            path: path.filter(|_| installed),
+        });
+    }
 
-    // Print detection results
-    print_editor_status("Claude Code", claude_installed, paths::claude_config_dir());
-    print_editor_status("Cursor", cursor_installed, paths::cursor_config_dir());
-    print_editor_status("VS Code", vscode_installed, None);
+    // List installer plugins (ax-installer-* on PATH or in ~/.ax/plugins)
+    let plugins = discover_plugins();
+    if !plugins.is_empty() {
+        say!("\n{} Installer plugins:\n", "→".cyan());
+        for plugin in &plugins {
+            print_editor_status(&plugin.name, true, Some(plugin.path.clone()));
+        }
+    }
 
-    println!();
+    say!();
 
-    // Determine default target
-    let default_target = if claude_installed {
-        "claude"
-    } else if cursor_installed {
-        "cursor"
-    } else {
-        "claude" // Default to claude even if not detected
-    };
+    // Determine default target (default to claude even if not detected)
+    let default_target = detected.first().copied().unwrap_or(Target::Claude).id();
 
-    // Create config
-    let config = ApmConfig::new(default_target.to_string());
+    // Create or update config
     let config_path = paths::ax_config_path()?;
-
-    // Ensure config directory exists
-    if let Some(parent) = config_path.parent() {
-        std::fs::create_dir_all(parent)?;
+    let config = write_config(&config_path, default_target)?;
+
+    if output::is_json() {
+        let plugins: Vec<EditorStatus> = plugins
+            .iter()
+            .map(|plugin| EditorStatus {
+                id: plugin.name.clone(),
+                name: plugin.name.clone(),
+                detected: true,
+                path: Some(plugin.path.clone()),
+            })
+            .collect();
+        return output::emit(&serde_json::json!({
+            "config_path": config_path,
+            "default_targets": config.default_targets,
+            "editors": editors,
+            "plugins": plugins,
+        }));
     }
 
-    // Write config
-    config.save(&config_path)?;
-
-    println!(
-        "{} Created configuration at {}",
+    say!(
+        "{} Saved configuration to {}",
         "✓".green().bold(),
         config_path.display().to_string().cyan()
     );
-    println!(
-        "{} Default target set to: {}",
+    say!(
+        "{} Default targets: {}",
         "✓".green().bold(),
-        default_target.cyan().bold()
+        config.default_targets.join(", ").cyan().bold()
     );
 
-    println!();
+    say!();
     ui::print_success("AX initialized successfully!");
-    println!(
+    say!(
         "\n  Run {} to see available agents.",
         "ax list".cyan().bold()
     );
+    if detected.len() > 1 {
+        say!(
+            "  Use {} to install to every detected editor at once.",
+            "--target all-detected".cyan().bold()
+        );
+    }
 
     Ok(())
 }
 
-fn detect_claude() -> bool {
-    paths::claude_config_dir()
-        .map(|path| path.exists())
-        .unwrap_or(false)
-}
+/// Add the default target to the config file, keeping every other setting
+///
+/// Targets already listed in an existing config are kept.
+fn write_config(path: &Path, default_target: &str) -> Result<ApmConfig> {
+    let mut config = if path.exists() {
+        ApmConfig::load(path)?
+    } else {
+        ApmConfig {
+            default_targets: Vec::new(),
+            ..ApmConfig::default()
+        }
+    };
+    if !config.default_targets.iter().any(|target| target == default_target) {
+        config.default_targets.push(default_target.to_string());
+    }
 
-fn detect_cursor() -> bool {
-    paths::cursor_config_dir()
-        .map(|path| path.exists())
-        .unwrap_or_else(|| {
-            // Also check for .cursor in current directory
-            PathBuf::from(".cursor").exists()
-        })
-}
+    // Ensure config directory exists
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
 
-fn detect_vscode() -> bool {
-    // Check if code command exists
-    which::which("code").is_ok()
+    config.save(path)?;
+    Ok(config)
 }
 
 fn print_editor_status(name: &str, installed: bool, path: Option<PathBuf>) {
@@ -103,14 +147,45 @@ fn print_editor_status(name: &str, installed: bool, path: Option<PathBuf>) {
         "not found".dimmed()
     };
 
-    print!("  {} {} - {}", status, name.bold(), status_text);
-
-    if installed {
-        if let Some(p) = path {
-            print!(" ({})", p.display().to_string().dimmed());
-        }
-    }
+    let location = match path {
+        Some(p) if installed => format!(" ({})", p.display().to_string().dimmed()),
+        _ => String::new(),
+    };
 
-    println!();
+    say!("  {} {} - {}{}", status, name.bold(), status_text, location);
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_keeps_existing_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".ax").join("config.toml");
+
+        let config = write_config(&path, "cursor").unwrap();
+        assert_eq!(config.default_targets, ["cursor"]);
+
+        std::fs::write(
+            &path,
+            "default_targets = [\"claude\", \"cursor\"]\nregistry_url = \"bundled\"\n\n[variables]\ncompany = \"Acme\"\n",
+        )
+        .unwrap();
+        let config = write_config(&path, "zed").unwrap();
+        let saved = ApmConfig::load(&path).unwrap();
+        assert_eq!(saved.default_targets, ["claude", "cursor", "zed"]);
+        assert_eq!(saved.registry_url, "bundled");
+        assert_eq!(saved.variables.get("company").map(String::as_str), Some("Acme"));
+        assert_eq!(config.registry_url, saved.registry_url);
+
+        // Running init again doesn't add the target twice
+        write_config(&path, "cursor").unwrap();
+        assert_eq!(ApmConfig::load(&path).unwrap().default_targets, ["claude", "cursor", "zed"]);
+
+        // A broken config is reported rather than overwritten
+        std::fs::write(&path, "default_targets = [").unwrap();
+        assert!(write_config(&path, "zed").is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "default_targets = [");
+    }
+}
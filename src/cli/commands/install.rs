@@ -1,151 +1,988 @@
 //! `apm install` Command
 //!
-//! Installs an agent configuration into the target editor.
+//! Installs an agent configuration into one or more target editors.
 
 use anyhow::{Context, Result};
 use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
-use crate::core::agent::AgentConfig;
+use crate::core::agent::{AgentConfig, McpTool};
+use crate::core::config::{ApmConfig, ProjectConfig};
+use crate::core::error::AxError;
 use crate::core::registry::Registry;
-use crate::installers::{get_installer, Target};
-use crate::utils::{ui, validation};
+use crate::core::resolver;
+use crate::core::secrets::{self, SecretStore};
+use crate::core::template::Variables;
+use crate::installers::{get_installer_in, Installer, Plugin, Target};
+use crate::say;
+use crate::utils::output::{self, ErrorReport};
+use crate::utils::validation::{self, MissingDependency};
+use crate::utils::paths::{self, BaseDirs};
+use crate::utils::{detect, ui};
 
 use super::super::TargetArg;
 
+/// How `${VAR}` secrets are supplied without prompting
+#[derive(Debug, Clone, Default)]
+pub struct EnvOptions {
+    /// Never prompt for missing values
+    pub yes: bool,
+    /// Values from `--env NAME=VALUE`
+    pub env: Vec<(String, String)>,
+    /// Files from `--env-file`
+    pub env_files: Vec<PathBuf>,
+    /// Fail when a placeholder written as a value can't be resolved
+    pub require_env: bool,
+    /// Write values into configs that can't reference the environment
+    pub plaintext_secrets: bool,
+}
+
 /// Execute the install command
-pub async fn execute(agent_name: &str, target: TargetArg, global: bool) -> Result<()> {
-    let target: Target = target.into();
+pub async fn execute(
+    agent_name: &str,
+    targets: &[TargetArg],
+    global: bool,
+    strict_deps: bool,
+    options: &EnvOptions,
+) -> Result<()> {
+    let targets = resolve_targets(targets)?;
+    let variables = load_variables()?;
 
     ui::print_header(&format!("Installing {}", agent_name));
 
-    // Step 1: Fetch agent from registry
+    // Step 1: Fetch agent from registry (once for all targets)
     let spinner = ui::create_spinner("Fetching agent configuration...");
 
     let registry = Registry::new();
-    let agent: AgentConfig = registry
-        .fetch_agent(agent_name)
-        .await
-        .context(format!("Agent '{}' not found in registry", agent_name))?;
+    let agent: AgentConfig = registry.fetch_agent(agent_name).await?;
 
     spinner.finish_with_message(format!("{} Found {} v{}", "✓".green(), agent.name, agent.version));
 
-    // Step 2: Validate required tools
-    println!("\n{} Checking dependencies...", "→".cyan());
-
-    let missing_tools = validation::check_agent_dependencies(&agent);
-    if !missing_tools.is_empty() {
-        println!();
-        for tool in &missing_tools {
-            println!(
-                "  {} {} is required but not found in PATH",
-                "⚠".yellow().bold(),
-                tool.as_str().bold()
+    // Step 2: Resolve `requires:` so dependencies are installed first
+    let agents = if agent.requires.is_empty() {
+        vec![agent]
+    } else {
+        let spinner = ui::create_spinner("Resolving dependencies...");
+        let resolution = resolver::resolve(&registry, agent).await?;
+        let names: Vec<String> = resolution
+            .dependencies()
+            .iter()
+            .map(|dep| format!("{} v{}", dep.name, dep.version))
+            .collect();
+        spinner.finish_with_message(format!(
+            "{} Resolved {} dependenc{}: {}",
+            "✓".green(),
+            names.len(),
+            if names.len() == 1 { "y" } else { "ies" },
+            names.join(", ")
+        ));
+        resolution.order
+    };
+    let agent = agents.last().expect("resolution includes the requested agent");
+
+    for current in &agents {
+        for tool in &current.mcp {
+            tool.validate().with_context(|| {
+                AxError::Validation(format!("Invalid MCP configuration in {}", current.name))
+            })?;
+        }
+    }
+
+    // Step 3: Validate required tools
+    let missing_dependencies = check_dependencies(&agents, strict_deps)?;
+
+    // Step 4: Look up MCP secrets (once for all targets), prompting only if
+    // some target needs the values written into its config
+    let needs_values = options.plaintext_secrets
+        && targets.iter().any(|target| !target.expands_env(global));
+    let secrets = collect_secrets(&agents, needs_values, options)?;
+
+    // Step 5: Install into each target, collecting failures instead of stopping
+    let (mut results, reports) = install_targets(
+        targets,
+        &BaseDirs::system(),
+        global,
+        &agents,
+        &variables,
+        &secrets,
+        options.plaintext_secrets,
+    );
+
+    // Targets that reference the environment need the secrets exported
+    let unset: Vec<String> = secret_names(&agents)
+        .into_iter()
+        .filter(|name| std::env::var(name).is_err())
+        .collect();
+    let needs_env = results
+        .iter()
+        .any(|(target, result)| result.is_ok() && target.expands_env(global));
+    let unset = if needs_env { unset } else { Vec::new() };
+
+    // Values passed on the command line only reach configs that inline them
+    let passed: Vec<&str> = unset
+        .iter()
+        .filter(|name| secrets.contains_key(*name))
+        .map(String::as_str)
+        .collect();
+    if !passed.is_empty() && (!options.env.is_empty() || !options.env_files.is_empty()) {
+        let referencing: Vec<String> = results
+            .iter()
+            .filter(|(target, result)| result.is_ok() && target.expands_env(global))
+            .map(|(target, _)| target.display_name())
+            .collect();
+        ui::print_warning(&format!(
+            "{} from --env/--env-file not written to {}, which reference the environment; \
+             export them where your editor starts",
+            passed.join(", "),
+            referencing.join(", ")
+        ));
+    }
+
+    if output::is_json() {
+        output::emit(&InstallReport {
+            agent: agent.name.clone(),
+            version: agent.version.clone(),
+            dependencies: agents[..agents.len() - 1]
+                .iter()
+                .map(|dep| format!("{}@{}", dep.name, dep.version))
+                .collect(),
+            ok: reports.iter().all(|report| report.ok),
+            targets: reports,
+            env_required: unset.clone(),
+            missing_dependencies,
+            warnings: output::take_warnings(),
+        })?;
+    }
+
+    // Summary
+    say!();
+    if results.len() > 1 {
+        say!("  {}", "Summary".bold());
+        for (target, result) in &results {
+            match result {
+                Ok(()) => say!("  {} {}", "✓".green().bold(), target.display_name()),
+                Err(err) => say!(
+                    "  {} {}: {:#}",
+                    "✗".red().bold(),
+                    target.display_name(),
+                    err
+                ),
+            }
+        }
+        say!();
+    }
+
+    let succeeded: Vec<&InstallTarget> = results
+        .iter()
+        .filter(|(_, result)| result.is_ok())
+        .map(|(target, _)| target)
+        .collect();
+    let failed = results.len() - succeeded.len();
+
+    if !succeeded.is_empty() {
+        let names: Vec<String> = succeeded.iter().map(|t| t.display_name()).collect();
+        ui::print_success(&format!(
+            "{} installed successfully to {}!",
+            agent.name,
+            names.join(", ")
+        ));
+
+        // Print next steps
+        say!("\n  {} Next steps:", "→".cyan());
+        for target in &succeeded {
+            if succeeded.len() > 1 {
+                say!("    {}:", target.display_name().bold());
+            }
+            match target {
+                InstallTarget::Builtin(target) => print_next_steps(*target),
+                InstallTarget::Plugin(plugin) => {
+                    say!(
+                        "    1. See the {} plugin's documentation for how to load the agent",
+                        plugin.name
+                    );
+                }
+            }
+        }
+
+        if !unset.is_empty() {
+            say!(
+                "\n  {} Set {} in the environment your editor starts from",
+                "→".cyan(),
+                unset.join(", ").bold()
             );
         }
-        println!();
-        println!(
-            "  {} Some MCP tools may not work without these dependencies.",
-            "!".yellow()
-        );
-        println!(
-            "  {} Install missing tools and try again, or continue anyway.",
-            "→".cyan()
-        );
-        println!();
+    }
+
+    if failed > 0 {
+        // A single target keeps its original error; several get a summary
+        if results.len() == 1 {
+            if let Some((_, Err(err))) = results.pop() {
+                return Err(err);
+            }
+        }
+        return Err(AxError::InstallFailed {
+            agent: agent.name.clone(),
+            failed,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Result of `ax install --output json`
+#[derive(Debug, Serialize)]
+struct InstallReport {
+    agent: String,
+    version: String,
+    /// Dependencies installed before the agent, as `name@version`
+    dependencies: Vec<String>,
+    ok: bool,
+    targets: Vec<TargetReport>,
+    /// Secrets the editors read from their environment that aren't set here
+    env_required: Vec<String>,
+    /// Tools the agent needs that aren't on PATH
+    missing_dependencies: Vec<MissingDependency>,
+    warnings: Vec<String>,
+}
+
+/// Outcome of installing into one target
+#[derive(Debug, Serialize)]
+struct TargetReport {
+    target: String,
+    name: String,
+    ok: bool,
+    files: Vec<PathBuf>,
+    mcp_servers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorReport>,
+}
+
+/// A resolved install destination: a built-in target or an installer plugin
+enum InstallTarget {
+    Builtin(Target),
+    Plugin(Plugin),
+}
+
+impl InstallTarget {
+    /// Key used for this target in an agent's `targets:` overrides
+    fn id(&self) -> &str {
+        match self {
+            InstallTarget::Builtin(target) => target.id(),
+            InstallTarget::Plugin(plugin) => &plugin.name,
+        }
+    }
+
+    fn display_name(&self) -> String {
+        match self {
+            InstallTarget::Builtin(target) => target.display_name().to_string(),
+            InstallTarget::Plugin(plugin) => format!("{} (plugin)", plugin.name),
+        }
+    }
+
+    fn installer(&self, global: bool, base: &BaseDirs) -> Box<dyn Installer> {
+        match self {
+            InstallTarget::Builtin(target) => get_installer_in(*target, global, base.clone()),
+            InstallTarget::Plugin(plugin) => Box::new(plugin.installer(global, base.clone())),
+        }
+    }
+
+    /// Whether the target's MCP config can refer to environment variables
+    fn expands_env(&self, global: bool) -> bool {
+        self.installer(global, &BaseDirs::system())
+            .env_reference("API_KEY")
+            .is_some()
+    }
+
+    fn is(&self, other: &InstallTarget) -> bool {
+        match (self, other) {
+            (InstallTarget::Builtin(a), InstallTarget::Builtin(b)) => a == b,
+            (InstallTarget::Plugin(a), InstallTarget::Plugin(b)) => a.name == b.name,
+            _ => false,
+        }
+    }
+}
+
+/// Expand `--target` values (or the configured defaults) into concrete targets
+fn resolve_targets(args: &[TargetArg]) -> Result<Vec<InstallTarget>> {
+    let args: Vec<TargetArg> = if args.is_empty() {
+        let config = ApmConfig::load_or_default()?;
+        config
+            .default_targets
+            .iter()
+            .map(|name| name.parse::<TargetArg>().map_err(anyhow::Error::msg))
+            .collect::<Result<_>>()
+            .context("Invalid default_targets in ~/.ax/config.toml")?
     } else {
-        println!("  {} All dependencies satisfied", "✓".green());
+        args.to_vec()
+    };
+
+    let mut targets = Vec::new();
+    for arg in args {
+        match arg {
+            TargetArg::Builtin(target) => targets.push(InstallTarget::Builtin(target)),
+            TargetArg::Plugin(name) => {
+                let plugin = Plugin::find(&name).with_context(|| {
+                    format!(
+                        "Unknown target '{}': no built-in target or ax-installer-{} plugin found",
+                        name, name
+                    )
+                })?;
+                targets.push(InstallTarget::Plugin(plugin));
+            }
+            TargetArg::AllDetected => {
+                let detected = detect::detected_targets();
+                if detected.is_empty() {
+                    anyhow::bail!("No supported editors detected; pass --target explicitly");
+                }
+                targets.extend(detected.into_iter().map(InstallTarget::Builtin));
+            }
+        }
     }
 
-    // Step 3: Get the appropriate installer
-    let installer = get_installer(target, global);
+    if targets.is_empty() {
+        targets.push(InstallTarget::Builtin(Target::Claude));
+    }
 
-    // Step 4: Install identity
-    let spinner = ui::create_spinner("Installing identity (system prompt)...");
-    installer.install_identity(&agent)?;
-    spinner.finish_with_message(format!("{} Identity installed", "✓".green()));
+    // Keep the first occurrence of each target
+    let mut unique: Vec<InstallTarget> = Vec::new();
+    for target in targets {
+        if !unique.iter().any(|t| t.is(&target)) {
+            unique.push(target);
+        }
+    }
 
-    // Step 5: Install skills
-    if !agent.skills.is_empty() {
-        let spinner = ui::create_spinner(&format!("Installing {} skill(s)...", agent.skills.len()));
-        installer.install_skills(&agent)?;
-        spinner.finish_with_message(format!(
-            "{} {} skill(s) installed",
-            "✓".green(),
-            agent.skills.len()
+    Ok(unique)
+}
+
+/// Load user template variables, project ax.toml taking precedence
+pub(crate) fn load_variables() -> Result<BTreeMap<String, String>> {
+    let mut variables = ApmConfig::load_or_default()?.variables;
+    variables.extend(ProjectConfig::load_or_default()?.variables);
+    Ok(variables)
+}
+
+/// Expand template variables in the system prompt and skill content
+fn render_templates(
+    agent: &AgentConfig,
+    target: &str,
+    installer: &dyn Installer,
+    base: &BaseDirs,
+    variables: &BTreeMap<String, String>,
+) -> Result<AgentConfig> {
+    let project_root = match base.project_dir() {
+        Some(project) => project.to_path_buf(),
+        None => std::env::current_dir()?,
+    };
+
+    let mut vars = Variables::new();
+    vars.extend(variables)
+        .set("agentName", agent.name.clone())
+        .set("target", target)
+        .set("projectRoot", project_root.display().to_string());
+
+    let mut agent = agent.clone();
+    agent.identity.system_prompt = vars
+        .render(&agent.identity.system_prompt)
+        .context("Invalid placeholder in system prompt")?;
+
+    for skill in &mut agent.skills {
+        // Prefer where the installer puts the skill's files, else its source
+        let base_dir = match installer.skill_dir(&agent.name, skill)? {
+            Some(dir) => Some(dir.display().to_string()),
+            None => skill
+                .source_dir
+                .as_ref()
+                .map(|dir| dir.display().to_string())
+                .or_else(|| skill.remote_base_url.clone()),
+        };
+
+        let mut skill_vars = vars.clone();
+        if let Some(base_dir) = base_dir {
+            skill_vars.set("baseDir", base_dir);
+        }
+        skill.content = skill_vars
+            .render(&skill.content)
+            .with_context(|| format!("Invalid placeholder in skill '{}'", skill.name))?;
+    }
+
+    Ok(agent)
+}
+
+/// Report MCP commands missing from PATH
+fn check_dependencies(agents: &[AgentConfig], strict: bool) -> Result<Vec<MissingDependency>> {
+    say!("\n{} Checking dependencies...", "→".cyan());
+
+    // Remote skill scripts are fetched with a blocking client
+    let mut missing: Vec<MissingDependency> = Vec::new();
+    for agent in agents {
+        let found = tokio::task::block_in_place(|| validation::check_agent_dependencies(agent));
+        for dep in found {
+            match missing.iter_mut().find(|m| m.tool == dep.tool) {
+                Some(existing) => existing.required_by.extend(dep.required_by),
+                None => missing.push(dep),
+            }
+        }
+    }
+
+    if missing.is_empty() {
+        say!("  {} All dependencies satisfied", "✓".green());
+        return Ok(missing);
+    }
+
+    say!();
+    for dep in &missing {
+        ui::print_warning(&format!(
+            "{} is required by {} but not found in PATH",
+            dep.tool,
+            dep.required_by.join(", ")
         ));
+        say!("      {} {}", "→".cyan(), dep.hint.dimmed());
     }
+    say!();
 
-    // Step 6: Install MCP tools
-    if !agent.mcp.is_empty() {
-        // Clone and mutate agent to add actual API keys
-        let mut agent_with_keys = agent.clone();
-        
-        // Check for MCPs that require API keys and prompt user
-        for tool in &mut agent_with_keys.mcp {
+    if strict {
+        let tools: Vec<&str> = missing.iter().map(|dep| dep.tool.as_str()).collect();
+        return Err(AxError::Validation(format!(
+            "Missing required tool(s): {} (drop --strict-deps to install anyway)",
+            tools.join(", ")
+        ))
+        .into());
+    }
+
+    say!(
+        "  {} Some skills or MCP tools may not work without these dependencies.",
+        "!".yellow()
+    );
+    say!(
+        "  {} Install missing tools and try again, or continue anyway.",
+        "→".cyan()
+    );
+    say!();
+    Ok(missing)
+}
+
+/// Resolve the `${VAR}` secrets referenced by MCP tools
+///
+/// Values come from `--env`, `--env-file`, the environment and
+/// ~/.ax/secrets.toml, in that order. When some target needs the values
+/// written into its config and stdin is a terminal, missing ones are
+/// prompted for with hidden input and saved.
+fn collect_secrets(
+    agents: &[AgentConfig],
+    needs_values: bool,
+    options: &EnvOptions,
+) -> Result<BTreeMap<String, String>> {
+    let mut provided = BTreeMap::new();
+    for path in &options.env_files {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let values = secrets::parse_env_file(&content)
+            .with_context(|| AxError::Parse(path.display().to_string()))?;
+        provided.extend(values);
+    }
+    provided.extend(options.env.iter().cloned());
+
+    let interactive = needs_values
+        && !options.yes
+        && !output::is_json()
+        && std::io::stdin().is_terminal();
+    let mut store = SecretStore::load_or_default()?;
+    let mut values = BTreeMap::new();
+    let mut prompted = BTreeSet::new();
+    let mut changed = false;
+
+    for tool in agents.iter().flat_map(|agent| &agent.mcp) {
+        for name in secret_fields(tool).flat_map(|value| secrets::placeholders(value)) {
+            if values.contains_key(name) {
+                continue;
+            }
+            if let Some(value) = provided.get(name).cloned().or_else(|| store.get(name)) {
+                values.insert(name.to_string(), value);
+                continue;
+            }
+            if !interactive || !prompted.insert(name.to_string()) {
+                continue;
+            }
+
+            say!();
+            say!("  {} MCP '{}' needs {}", "ℹ".blue().bold(), tool.name.bold(), name.bold());
             if let Some(url) = &tool.setup_url {
-                println!();
-                println!("  {} MCP '{}' requires an API key", "ℹ".blue().bold(), tool.name.bold());
-                println!("  {} Get your API key here: {}", "→".cyan(), url.underline().blue());
-                println!();
-                print!("  {} Paste your API key (or press Enter to skip): ", "?".yellow().bold());
-                
-                // Flush stdout to ensure prompt is shown
-                use std::io::Write;
-                std::io::stdout().flush().ok();
-                
-                // Read API key from user
-                let mut api_key = String::new();
-                if std::io::stdin().read_line(&mut api_key).is_ok() {
-                    let api_key = api_key.trim();
-                    if !api_key.is_empty() {
-                        // Replace placeholder with actual key in env
-                        for (_, value) in tool.env.iter_mut() {
-                            if value.starts_with("${") && value.ends_with("}") {
-                                *value = api_key.to_string();
-                            }
-                        }
-                        println!("  {} API key configured!", "✓".green());
-                    } else {
-                        println!("  {} Skipped - you can configure this later", "→".cyan());
-                    }
-                }
+                say!("  {} Get your API key here: {}", "→".cyan(), url.underline().blue());
+            }
+            say!();
+
+            let prompt = format!(
+                "  {} Paste {} (input is hidden, press Enter to skip): ",
+                "?".yellow().bold(),
+                name
+            );
+            let term = console::Term::stdout();
+            let value = term
+                .write_str(&prompt)
+                .and_then(|()| term.read_secure_line())
+                .unwrap_or_default();
+
+            let value = value.trim();
+            if value.is_empty() {
+                say!("  {} Skipped - you can configure this later", "→".cyan());
+            } else {
+                store.set(name, value);
+                values.insert(name.to_string(), value.to_string());
+                changed = true;
+                say!("  {} {} saved", "✓".green(), name);
             }
         }
+    }
 
-        let spinner = ui::create_spinner(&format!("Configuring {} MCP tool(s)...", agent_with_keys.mcp.len()));
-        installer.install_tools(&agent_with_keys)?;
-        spinner.finish_with_message(format!(
-            "{} {} MCP tool(s) configured",
-            "✓".green(),
-            agent_with_keys.mcp.len()
-        ));
+    if changed {
+        let path = paths::ax_secrets_path()?;
+        store.save(&path)?;
+        say!("  {} Secrets stored in {}", "→".cyan(), path.display());
+    }
+
+    // Targets that reference the environment don't need the values here
+    if options.require_env && needs_values {
+        let missing: Vec<String> = secret_names(agents)
+            .into_iter()
+            .filter(|name| !values.contains_key(name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(AxError::Validation(format!(
+                "Unresolved environment variable(s): {} (pass them with --env NAME=value or --env-file)",
+                missing.join(", ")
+            ))
+            .into());
+        }
     }
 
-    // Success message
-    println!();
-    ui::print_success(&format!(
-        "{} installed successfully to {}!",
-        agent.name,
-        target.display_name()
-    ));
+    Ok(values)
+}
+
+/// Names of every `${VAR}` referenced by the agents' MCP tools
+fn secret_names(agents: &[AgentConfig]) -> BTreeSet<String> {
+    agents
+        .iter()
+        .flat_map(|agent| &agent.mcp)
+        .flat_map(secret_fields)
+        .flat_map(|value| secrets::placeholders(value))
+        .map(str::to_string)
+        .collect()
+}
+
+/// MCP tool values that may contain `${VAR}` placeholders
+pub(crate) fn secret_fields(tool: &McpTool) -> impl Iterator<Item = &String> {
+    tool.args
+        .iter()
+        .chain(tool.env.values())
+        .chain(tool.headers.values())
+        .chain(tool.url.iter())
+}
+
+/// Rewrite `${VAR}` placeholders for a target
+///
+/// Targets that expand environment variables get their own reference
+/// syntax; the others get the secret's value. Returns the names of secrets
+/// written as plain text.
+fn apply_secrets(
+    agent: &mut AgentConfig,
+    installer: &dyn Installer,
+    secrets: &BTreeMap<String, String>,
+) -> BTreeSet<String> {
+    let mut inlined = BTreeSet::new();
+    let mut replace = |name: &str| match installer.env_reference(name) {
+        Some(reference) => Some(reference),
+        None => {
+            let value = secrets.get(name)?.clone();
+            inlined.insert(name.to_string());
+            Some(value)
+        }
+    };
+
+    for tool in &mut agent.mcp {
+        let values = tool
+            .args
+            .iter_mut()
+            .chain(tool.env.values_mut())
+            .chain(tool.headers.values_mut())
+            .chain(tool.url.iter_mut());
+        for value in values {
+            *value = secrets::substitute(value, &mut replace);
+        }
+    }
+
+    inlined
+}
+
+/// The agent as a target's installer sees it
+///
+/// Merges the agent's per-target overrides over its defaults, expands
+/// template variables and rewrites `${VAR}` secrets; placeholders without a
+/// value are left as they are. Returns the names of secrets written as
+/// plain text.
+pub(crate) fn prepare_for_target(
+    agent: &AgentConfig,
+    target: &str,
+    installer: &dyn Installer,
+    base: &BaseDirs,
+    variables: &BTreeMap<String, String>,
+    secrets: &BTreeMap<String, String>,
+) -> Result<(AgentConfig, BTreeSet<String>)> {
+    let agent = agent.for_target(target)?;
+    let mut agent = render_templates(&agent, target, installer, base, variables)?;
+    let inlined = apply_secrets(&mut agent, installer, secrets);
+    Ok((agent, inlined))
+}
+
+/// Install every agent into each target in turn, reporting what each wrote
+fn install_targets(
+    targets: Vec<InstallTarget>,
+    base: &BaseDirs,
+    global: bool,
+    agents: &[AgentConfig],
+    variables: &BTreeMap<String, String>,
+    secrets: &BTreeMap<String, String>,
+    plaintext_secrets: bool,
+) -> (Vec<(InstallTarget, Result<()>)>, Vec<TargetReport>) {
+    let target_count = targets.len();
+    let mut results = Vec::new();
+    let mut reports = Vec::new();
+    output::take_written();
+    for target in targets {
+        if target_count > 1 {
+            say!("\n{} {}", "→".cyan(), target.display_name().bold());
+        }
+        let mut mcp_servers = Vec::new();
+        let result = agents.iter().try_for_each(|current| {
+            if agents.len() > 1 {
+                say!("  {} {} v{}", "•".dimmed(), current.name, current.version);
+            }
+            let servers =
+                install_to_target(&target, base, global, current, variables, secrets, plaintext_secrets)
+                    .with_context(|| format!("Failed to install {}", current.name))?;
+            mcp_servers.extend(servers);
+            Ok(())
+        });
+        reports.push(TargetReport {
+            target: target.id().to_string(),
+            name: target.display_name(),
+            ok: result.is_ok(),
+            files: output::take_written(),
+            mcp_servers,
+            error: result.as_ref().err().map(ErrorReport::new),
+        });
+        results.push((target, result));
+    }
+    (results, reports)
+}
+
+/// Run the identity, skills and MCP steps for a single target, returning
+/// the MCP servers configured
+fn install_to_target(
+    target: &InstallTarget,
+    base: &BaseDirs,
+    global: bool,
+    agent: &AgentConfig,
+    variables: &BTreeMap<String, String>,
+    secrets: &BTreeMap<String, String>,
+    plaintext_secrets: bool,
+) -> Result<Vec<String>> {
+    let installer = target.installer(global, base);
+    let mcp_config = installer.mcp_config_path()?;
+
+    // Secrets only reach a config file as plain text when asked for
+    let names = secret_names(std::slice::from_ref(agent));
+    if !names.is_empty() && installer.env_reference("API_KEY").is_none() && !plaintext_secrets {
+        return Err(AxError::Validation(format!(
+            "{} can't read environment variables in its MCP config; pass --plaintext-secrets to write {} into {}",
+            target.display_name(),
+            names.into_iter().collect::<Vec<_>>().join(", "),
+            mcp_config
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "its config".to_string())
+        ))
+        .into());
+    }
+
+    let (agent, inlined) =
+        prepare_for_target(agent, target.id(), installer.as_ref(), base, variables, secrets)?;
+    let agent = &agent;
+    let inlined = inlined.into_iter().collect::<Vec<_>>().join(", ");
+
+    // Installers may download skill files with a blocking client
+    let failed = || AxError::Installer(target.display_name());
+    if !agent.skill_only {
+        run_step("Installing identity (system prompt)...", "Identity installed", || {
+            tokio::task::block_in_place(|| installer.install_identity(agent)).with_context(failed)
+        })?;
+    }
+
+    if !agent.skills.is_empty() {
+        run_step(
+            &format!("Installing {} skill(s)...", agent.skills.len()),
+            &format!("{} skill(s) installed", agent.skills.len()),
+            || tokio::task::block_in_place(|| installer.install_skills(agent)).with_context(failed),
+        )?;
+    }
 
-    // Print next steps
-    println!("\n  {} Next steps:", "→".cyan());
+    if !agent.mcp.is_empty() {
+        run_step(
+            &format!("Configuring {} MCP tool(s)...", agent.mcp.len()),
+            &format!("{} MCP tool(s) configured", agent.mcp.len()),
+            || tokio::task::block_in_place(|| installer.install_tools(agent)).with_context(failed),
+        )?;
+        if !inlined.is_empty() {
+            ui::print_warning(&format!(
+                "{} can't read environment variables in its MCP config; {} written in plain text",
+                target.display_name(),
+                inlined
+            ));
+            if let Some(path) = mcp_config.filter(|path| in_project(path, base)) {
+                ui::print_warning(&format!(
+                    "{} is inside the project; keep it out of version control",
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    Ok(agent.mcp.iter().map(|tool| tool.name.clone()).collect())
+}
+
+/// Whether a file an installer writes is inside the project directory
+fn in_project(path: &Path, base: &BaseDirs) -> bool {
+    match base.project_dir() {
+        Some(project) => path.starts_with(project),
+        None => {
+            path.is_relative()
+                || std::env::current_dir().is_ok_and(|cwd| path.starts_with(cwd))
+        }
+    }
+}
+
+/// Run one install step behind a spinner
+fn run_step(message: &str, done: &str, step: impl FnOnce() -> Result<()>) -> Result<()> {
+    let spinner = ui::create_spinner(message);
+    match step() {
+        Ok(()) => {
+            spinner.finish_with_message(format!("{} {}", "✓".green(), done));
+            Ok(())
+        }
+        Err(err) => {
+            spinner.abandon_with_message(format!("{} {}", "✗".red(), message.trim_end_matches('.')));
+            Err(err)
+        }
+    }
+}
+
+fn print_next_steps(target: Target) {
     match target {
         Target::Claude => {
-            println!("    1. Restart Claude Code to load the new agent");
-            println!("    2. The agent will be available in your conversations");
+            say!("    1. Restart Claude Code to load the new agent");
+            say!("    2. The agent will be available in your conversations");
         }
         Target::Cursor => {
-            println!("    1. Restart Cursor to load the new rules");
-            println!("    2. The agent context will be available in Composer");
+            say!("    1. Restart Cursor to load the new rules");
+            say!("    2. The agent context will be available in Composer");
         }
         Target::Codex => {
-            println!("    1. Restart Codex to load the new agent");
-            println!("    2. The agent will be available in your conversations");
+            say!("    1. Restart Codex to load the new agent");
+            say!("    2. The agent will be available in your conversations");
+        }
+        Target::Windsurf => {
+            say!("    1. Restart Windsurf to load the new rules and MCP servers");
+            say!("    2. The agent context will be available in Cascade");
+        }
+        Target::Copilot => {
+            say!("    1. Reload the VS Code window to pick up the new instructions");
+            say!("    2. Start the MCP servers from the MCP view in Copilot Chat");
+        }
+        Target::Gemini => {
+            say!("    1. Restart Gemini CLI (or run /memory refresh) to load GEMINI.md");
+            say!("    2. Run /mcp to check the configured MCP servers");
+        }
+        Target::Cline => {
+            say!("    1. Reload the VS Code window to pick up the new rules");
+            say!("    2. Check the MCP Servers tab in Cline for the new tools");
+        }
+        Target::Roo => {
+            say!("    1. Reload the VS Code window to pick up the new rules");
+            say!("    2. Switch to the agent's custom mode in Roo Code");
+        }
+        Target::Zed => {
+            say!("    1. Reopen the project in Zed to load the .rules file");
+            say!("    2. Check the Agent Panel settings for the new context servers");
+        }
+        Target::Continue => {
+            say!("    1. Reload the Continue extension to pick up the new rules");
+            say!("    2. MCP servers are available in Continue's agent mode");
+        }
+        Target::Opencode => {
+            say!("    1. Restart opencode to load AGENTS.md and opencode.json");
+            say!("    2. The agent will be available in your sessions");
         }
     }
-
-    Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_json_report_lists_written_files() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let base = BaseDirs::new(dir.path().join("home"), &project);
+
+        let script = dir.path().join("ax-installer-acme");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\ncat > /dev/null\necho '{\"files\": [\"acme/demo.md\"]}'\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let plugin = Plugin {
+            name: "acme".to_string(),
+            path: script,
+        };
+
+        let agent: AgentConfig = serde_yaml::from_str(
+            "name: demo\nversion: 1.0.0\ndescription: d\nauthor: a\nidentity:\n  system_prompt: hi\n",
+        )
+        .unwrap();
+        let targets = vec![InstallTarget::Builtin(Target::Cursor), InstallTarget::Plugin(plugin)];
+
+        let (_, reports) =
+            install_targets(targets, &base, false, &[agent], &BTreeMap::new(), &BTreeMap::new(), false);
+        let report = serde_json::to_value(&reports).unwrap();
+
+        let files = |index: usize| -> Vec<String> {
+            report[index]["files"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|file| {
+                    let file = Path::new(file.as_str().unwrap());
+                    file.strip_prefix(&project).unwrap().display().to_string()
+                })
+                .collect()
+        };
+        assert_eq!(report[0]["target"], "cursor");
+        assert_eq!(files(0), [".cursor/rules/demo-identity.mdc"]);
+        assert_eq!(report[1]["target"], "acme");
+        assert_eq!(report[1]["ok"], true);
+        assert_eq!(files(1), ["acme/demo.md"]);
+    }
+
+    #[test]
+    fn test_plain_text_secrets_need_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let base = BaseDirs::new(dir.path().join("home"), &project);
+
+        let agent: AgentConfig = serde_yaml::from_str(
+            r#"
+name: demo
+version: 1.0.0
+description: d
+author: a
+identity:
+  system_prompt: hi
+mcp:
+  - name: search
+    command: npx
+    env:
+      SEARCH_KEY: ${SEARCH_KEY}
+"#,
+        )
+        .unwrap();
+        let secrets = BTreeMap::from([("SEARCH_KEY".to_string(), "s3cret".to_string())]);
+        let install = |plaintext_secrets| {
+            let targets = vec![
+                InstallTarget::Builtin(Target::Continue),
+                InstallTarget::Builtin(Target::Zed),
+            ];
+            let (_, reports) = install_targets(
+                targets,
+                &base,
+                false,
+                std::slice::from_ref(&agent),
+                &BTreeMap::new(),
+                &secrets,
+                plaintext_secrets,
+            );
+            serde_json::to_value(&reports).unwrap()
+        };
+
+        // Continue references the secret; Zed refuses to write it
+        let report = install(false);
+        assert_eq!(report[0]["ok"], true);
+        assert_eq!(report[1]["ok"], false);
+        let search = std::fs::read_to_string(project.join(".continue/mcpServers/search.yaml")).unwrap();
+        assert!(search.contains("SEARCH_KEY: ${{ secrets.SEARCH_KEY }}"));
+        assert!(!project.join(".zed/settings.json").exists());
+
+        let report = install(true);
+        assert_eq!(report[1]["ok"], true);
+        let settings = std::fs::read_to_string(project.join(".zed/settings.json")).unwrap();
+        assert!(settings.contains("s3cret"));
+    }
+
+    #[test]
+    fn test_skill_dependency_installs_no_identity() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = BaseDirs::new(dir.path().join("home"), dir.path());
+        let mut skill: AgentConfig = serde_yaml::from_str(
+            "name: lint\nversion: 1.0.0\ndescription: d\nauthor: a\nidentity:\n  system_prompt: You have the lint skill installed.\nskills:\n  - name: lint\n    content: Run the linter\n",
+        )
+        .unwrap();
+        skill.skill_only = true;
+
+        let (results, _) = install_targets(
+            vec![InstallTarget::Builtin(Target::Cursor)],
+            &base,
+            false,
+            &[skill],
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+            false,
+        );
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+        assert!(dir.path().join(".cursor/rules/lint-lint.mdc").exists());
+        assert!(!dir.path().join(".cursor/rules/lint-identity.mdc").exists());
+    }
+
+    #[test]
+    fn test_project_root_from_base_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let base = BaseDirs::new(dir.path().join("home"), &project);
+        let agent: AgentConfig = serde_yaml::from_str(
+            "name: demo\nversion: 1.0.0\ndescription: d\nauthor: a\nidentity:\n  system_prompt: Work in {projectRoot}\n",
+        )
+        .unwrap();
+
+        let (results, _) = install_targets(
+            vec![InstallTarget::Builtin(Target::Cursor)],
+            &base,
+            false,
+            &[agent],
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+            false,
+        );
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+        let identity = std::fs::read_to_string(project.join(".cursor/rules/demo-identity.mdc")).unwrap();
+        assert!(identity.contains(&format!("Work in {}", project.display())));
+    }
+}
@@ -7,7 +7,7 @@ use colored::Colorize;
 
 use crate::core::agent::AgentInfo;
 use crate::core::registry::Registry;
-use crate::utils::ui;
+use crate::utils::{output, ui};
 
 /// Execute the list command
 pub async fn execute() -> Result<()> {
@@ -20,6 +20,13 @@ pub async fn execute() -> Result<()> {
 
     spinner.finish_and_clear();
 
+    if output::is_json() {
+        return output::emit(&serde_json::json!({
+            "agents": agents,
+            "warnings": output::take_warnings(),
+        }));
+    }
+
     if agents.is_empty() {
         println!("  {} No agents found in registry.", "!".yellow().bold());
         return Ok(());
@@ -37,17 +44,11 @@ pub async fn execute() -> Result<()> {
 
     // Print agents
     for agent in &agents {
-        let description = if agent.description.len() > 38 {
-            format!("{}...", &agent.description[..35])
-        } else {
-            agent.description.clone()
-        };
-
         println!(
-            "  {:<20} {:<10} {:<40} {}",
+            "  {:<20} {:<10} {} {}",
             agent.name.green(),
             agent.version.dimmed(),
-            description,
+            ui::fit_column(&agent.description, 40),
             agent.author.dimmed()
         );
     }
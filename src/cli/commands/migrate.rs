@@ -0,0 +1,243 @@
+//! `ax migrate` Command
+//!
+//! Rewrites agent.yaml and SKILL.md files to the current schema version.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::error::AxError;
+use crate::core::schema::{self, CURRENT_SCHEMA};
+use crate::say;
+use crate::utils::{output, ui};
+
+/// Execute the migrate command
+pub async fn execute(path: &Path, dry_run: bool) -> Result<()> {
+    ui::print_header("Migrating Definitions");
+
+    let files = collect_files(path)?;
+    if files.is_empty() && !output::is_json() {
+        say!(
+            "  {} No agent YAML or SKILL.md files found in {}",
+            "!".yellow().bold(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let mut migrated = 0;
+    let mut failed = 0;
+    let mut report = Vec::new();
+    for file in &files {
+        match migrate_file(file, dry_run) {
+            Ok(Outcome::Migrated(from)) => {
+                migrated += 1;
+                report.push(serde_json::json!({ "path": file, "from": from }));
+                say!(
+                    "  {} {} (schema {} → {})",
+                    "✓".green().bold(),
+                    file.display(),
+                    from,
+                    CURRENT_SCHEMA
+                );
+            }
+            Ok(Outcome::UpToDate) => {
+                report.push(serde_json::json!({ "path": file, "from": null }));
+                say!(
+                    "  {} {} {}",
+                    "•".dimmed(),
+                    file.display(),
+                    "up to date".dimmed()
+                );
+            }
+            Ok(Outcome::Skipped(reason)) => {
+                report.push(serde_json::json!({ "path": file, "skipped": reason }));
+                say!(
+                    "  {} {} {}",
+                    "-".dimmed(),
+                    file.display(),
+                    format!("skipped: {}", reason).dimmed()
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                report.push(serde_json::json!({ "path": file, "error": format!("{:#}", e) }));
+                say!("  {} {}: {:#}", "✗".red().bold(), file.display(), e);
+            }
+        }
+    }
+
+    if output::is_json() {
+        output::emit(&serde_json::json!({
+            "schema": CURRENT_SCHEMA,
+            "dry_run": dry_run,
+            "files": report,
+        }))?;
+    } else {
+        println!();
+    }
+    if failed > 0 {
+        anyhow::bail!("{} file(s) could not be migrated", failed);
+    }
+    if output::is_json() {
+        return Ok(());
+    }
+
+    if migrated == 0 {
+        ui::print_success("Everything is already on the current schema");
+    } else if dry_run {
+        ui::print_warning(&format!(
+            "{} file(s) need migrating (dry run, nothing written)",
+            migrated
+        ));
+    } else {
+        ui::print_success(&format!("Migrated {} file(s)", migrated));
+        println!(
+            "\n  {} YAML comments in rewritten files are not preserved; review the diff.",
+            "→".cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Build, dependency and VCS directories that never hold definitions to migrate
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", "vendor", "dist", "build", "venv", "__pycache__"];
+
+/// What happened to one file
+enum Outcome {
+    /// Upgraded from this schema version
+    Migrated(u32),
+    UpToDate,
+    /// Not an ax definition, with the reason
+    Skipped(&'static str),
+}
+
+/// Find agent YAML files and SKILL.md files under `path`
+fn collect_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = Vec::new();
+    let mut entries: Vec<_> = fs::read_dir(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    for entry in entries {
+        let name = entry.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if entry.is_dir() {
+            if !SKIPPED_DIRS.contains(&name) {
+                files.extend(collect_files(&entry)?);
+            }
+        } else if name == "SKILL.md" || name.ends_with(".yaml") || name.ends_with(".yml") {
+            files.push(entry);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Whether a parsed YAML file is an agent definition: a mapping with a
+/// `name` and an `identity` or `skills`, or any mapping under `agents/`
+fn is_agent(path: &Path, doc: &serde_yaml::Value) -> bool {
+    let in_agents_dir = path
+        .parent()
+        .is_some_and(|dir| dir.components().any(|c| c.as_os_str() == "agents"));
+    in_agents_dir || (doc.get("name").is_some() && (doc.get("identity").is_some() || doc.get("skills").is_some()))
+}
+
+/// Migrate one file, skipping anything that isn't an ax definition
+fn migrate_file(path: &Path, dry_run: bool) -> Result<Outcome> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let is_skill = path.file_name().is_some_and(|name| name == "SKILL.md");
+
+    let (yaml, body) = if is_skill {
+        match schema::split_frontmatter(&content) {
+            Some((frontmatter, body)) => (frontmatter, Some(body)),
+            None => return Ok(Outcome::Skipped("no frontmatter")),
+        }
+    } else {
+        (content.as_str(), None)
+    };
+
+    let mut doc: serde_yaml::Value = match serde_yaml::from_str(yaml) {
+        Ok(doc) => doc,
+        Err(e) if is_skill => return Err(e).with_context(|| AxError::Parse(path.display().to_string())),
+        Err(_) => return Ok(Outcome::Skipped("not valid YAML")),
+    };
+    if !doc.is_mapping() {
+        return Ok(Outcome::Skipped("not a YAML mapping"));
+    }
+    if !is_skill && !is_agent(path, &doc) {
+        return Ok(Outcome::Skipped("not an agent definition"));
+    }
+    let had_schema = doc.get("schema").is_some();
+
+    let from = if is_skill {
+        schema::migrate_skill(&mut doc)
+    } else {
+        schema::migrate_agent(&mut doc)
+    }
+    .with_context(|| format!("Failed to migrate {}", path.display()))?;
+
+    if from == CURRENT_SCHEMA && had_schema {
+        return Ok(Outcome::UpToDate);
+    }
+
+    if !dry_run {
+        let yaml = serde_yaml::to_string(&doc)?;
+        let updated = match body {
+            Some(body) => format!("---\n{}---\n\n{}", yaml, body),
+            None => yaml,
+        };
+        fs::write(path, updated).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    Ok(Outcome::Migrated(from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AGENT: &str = "name: demo\nversion: 1.0.0\ndescription: d\nauthor: a\nidentity:\n  system_prompt: hi\n";
+
+    #[tokio::test]
+    async fn test_non_agent_yaml_left_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let workflow = "name: CI\non: push\njobs:\n  test:\n    runs-on: ubuntu-latest # pinned\n";
+        fs::write(dir.path().join("ci.yml"), workflow).unwrap();
+        fs::write(dir.path().join("agent.yaml"), AGENT).unwrap();
+        fs::create_dir_all(dir.path().join("node_modules/pkg")).unwrap();
+        fs::write(dir.path().join("node_modules/pkg/agent.yaml"), AGENT).unwrap();
+
+        execute(dir.path(), false).await.unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("ci.yml")).unwrap(), workflow);
+        assert_eq!(fs::read_to_string(dir.path().join("node_modules/pkg/agent.yaml")).unwrap(), AGENT);
+        assert!(fs::read_to_string(dir.path().join("agent.yaml")).unwrap().contains("schema: 2"));
+    }
+
+    #[tokio::test]
+    async fn test_list_yaml_does_not_abort() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("list.yaml"), "- one\n- two\n").unwrap();
+        fs::write(dir.path().join("z-agent.yaml"), AGENT).unwrap();
+
+        execute(dir.path(), true).await.unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("z-agent.yaml")).unwrap(), AGENT);
+
+        execute(dir.path(), false).await.unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("list.yaml")).unwrap(), "- one\n- two\n");
+        assert!(fs::read_to_string(dir.path().join("z-agent.yaml")).unwrap().contains("schema: 2"));
+    }
+}
@@ -1,5 +1,11 @@
 //! CLI Commands Module
 
+pub mod cache;
+pub mod info;
 pub mod init;
 pub mod install;
 pub mod list;
+pub mod migrate;
+pub mod schema;
+pub mod search;
+pub mod tree;
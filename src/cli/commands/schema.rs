@@ -0,0 +1,15 @@
+//! `ax schema` Command
+//!
+//! Prints the JSON Schema for agent.yaml, SKILL.md frontmatter or config.toml.
+
+use anyhow::Result;
+use std::io::Write;
+
+use crate::core::json_schema::{self, SchemaKind};
+
+/// Execute the schema command
+pub async fn execute(kind: SchemaKind) -> Result<()> {
+    // Plain output so it can be redirected straight into a file
+    std::io::stdout().write_all(json_schema::generate(kind).as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! `ax search` Command
+//!
+//! Searches the registry for agents and skills matching a query.
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::core::agent::{AgentInfo, EntryKind};
+use crate::core::registry::Registry;
+use crate::core::search::{self, SearchFilters};
+use crate::utils::{output, ui};
+
+/// A ranked result for `--output json`
+#[derive(Serialize)]
+struct SearchResult<'a> {
+    #[serde(flatten)]
+    agent: &'a AgentInfo,
+    score: u32,
+}
+
+/// Execute the search command
+pub async fn execute(query: &str, author: Option<String>, target: Option<String>) -> Result<()> {
+    if query.trim().is_empty() {
+        ui::print_header("Search Results");
+    } else {
+        ui::print_header(&format!("Search Results for \"{}\"", query.trim()));
+    }
+
+    let spinner = ui::create_spinner("Fetching registry...");
+    let registry = Registry::new();
+    let agents: Vec<AgentInfo> = registry.fetch_agents().await?;
+    spinner.finish_and_clear();
+
+    let filters = SearchFilters { author, target };
+    let hits = search::search(&agents, query, &filters);
+
+    if output::is_json() {
+        let results: Vec<SearchResult> = hits
+            .iter()
+            .map(|hit| SearchResult { agent: hit.agent, score: hit.score })
+            .collect();
+        return output::emit(&serde_json::json!({
+            "query": query,
+            "results": results,
+            "warnings": output::take_warnings(),
+        }));
+    }
+
+    if hits.is_empty() {
+        println!("  {} No agents or skills match.", "!".yellow().bold());
+        return Ok(());
+    }
+
+    println!(
+        "  {:<20} {:<6} {:<10} {:<40} {}",
+        "NAME".bold().cyan(),
+        "KIND".bold().cyan(),
+        "VERSION".bold().cyan(),
+        "DESCRIPTION".bold().cyan(),
+        "AUTHOR".bold().cyan()
+    );
+    println!("  {}", "─".repeat(92).dimmed());
+
+    for hit in &hits {
+        let agent = hit.agent;
+        let kind = match agent.kind {
+            EntryKind::Agent => agent.kind.to_string().normal(),
+            EntryKind::Skill => agent.kind.to_string().magenta(),
+        };
+        println!(
+            "  {} {:<6} {:<10} {} {}",
+            ui::fit_column(&agent.name, 20).green(),
+            kind,
+            agent.version.dimmed(),
+            ui::fit_column(&agent.description, 40),
+            agent.author.dimmed()
+        );
+        if !agent.tags.is_empty() {
+            let tags: Vec<String> = agent.tags.iter().map(|tag| format!("#{}", tag)).collect();
+            println!("  {:<20} {}", "", tags.join(" ").dimmed());
+        }
+    }
+
+    println!();
+    println!(
+        "  {} {} result(s) of {} registry entries",
+        "→".cyan(),
+        hits.len().to_string().bold(),
+        agents.len()
+    );
+    println!(
+        "  {} Install with: {}",
+        "→".cyan(),
+        "ax install <agent-name>".cyan().bold()
+    );
+
+    Ok(())
+}
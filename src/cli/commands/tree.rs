@@ -0,0 +1,111 @@
+//! `ax tree` Command
+//!
+//! Prints the resolved dependency graph of an agent.
+
+use anyhow::Result;
+use colored::Colorize;
+use semver::VersionReq;
+use serde::Serialize;
+
+use crate::core::agent::AgentConfig;
+use crate::core::registry::Registry;
+use crate::core::resolver::{self, Resolution};
+use crate::utils::{output, ui};
+
+/// A node of the dependency tree in the `--output json` report
+#[derive(Debug, Serialize)]
+struct TreeNode {
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requirement: Option<String>,
+    dependencies: Vec<TreeNode>,
+}
+
+/// Execute the tree command
+pub async fn execute(agent_name: &str) -> Result<()> {
+    let spinner = ui::create_spinner("Resolving dependencies...");
+
+    let registry = Registry::new();
+    let resolution = match registry.fetch_agent(agent_name).await {
+        Ok(agent) => resolver::resolve(&registry, agent).await,
+        Err(err) => Err(err),
+    };
+
+    spinner.finish_and_clear();
+    let resolution = resolution?;
+
+    let root = resolution.root();
+    if output::is_json() {
+        return output::emit(&tree_node(&resolution, root, None));
+    }
+
+    println!("{} {}", root.name.bold(), format!("v{}", root.version).dimmed());
+
+    let mut shown = vec![root.name.clone()];
+    print_children(&resolution, root, "", &mut shown);
+
+    Ok(())
+}
+
+/// Build the JSON tree below an agent
+fn tree_node(resolution: &Resolution, agent: &AgentConfig, requirement: Option<String>) -> TreeNode {
+    let dependencies = agent
+        .requires
+        .iter()
+        .filter_map(|requirement| {
+            let dependency = resolution.get(&requirement.name)?;
+            let range = requirement.version.to_string();
+            Some(tree_node(resolution, dependency, Some(range)))
+        })
+        .collect();
+
+    TreeNode {
+        name: agent.name.clone(),
+        version: agent.version.clone(),
+        requirement,
+        dependencies,
+    }
+}
+
+/// Print an agent's requirements, marking already expanded entries with (*)
+fn print_children(
+    resolution: &Resolution,
+    agent: &AgentConfig,
+    prefix: &str,
+    shown: &mut Vec<String>,
+) {
+    for (index, requirement) in agent.requires.iter().enumerate() {
+        let last = index + 1 == agent.requires.len();
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+
+        let Some(dependency) = resolution.get(&requirement.name) else {
+            continue;
+        };
+
+        let range = if requirement.version == VersionReq::STAR {
+            String::new()
+        } else {
+            format!(" ({})", requirement.version)
+        };
+        let repeated = shown.contains(&dependency.name);
+        println!(
+            "{}{}{} {}{}{}",
+            prefix,
+            branch,
+            dependency.name,
+            format!("v{}", dependency.version).dimmed(),
+            range.dimmed(),
+            if repeated { " (*)" } else { "" }
+        );
+
+        if !repeated {
+            shown.push(dependency.name.clone());
+            print_children(resolution, dependency, &format!("{}{}", prefix, indent), shown);
+        }
+    }
+}
@@ -2,9 +2,13 @@
 
 pub mod commands;
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::str::FromStr;
 
+use crate::core::json_schema::SchemaKind;
 use crate::installers::Target;
+use crate::utils::output::OutputFormat;
 
 /// AX (Agent Package Manager) - The npm of the Agentic AI era
 ///
@@ -18,6 +22,14 @@ use crate::installers::Target;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Output format: colored text, or a single JSON document for scripts
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    /// Work from ~/.ax/cache only, without network access
+    #[arg(long, global = true)]
+    pub offline: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -28,34 +40,147 @@ pub enum Commands {
     /// List available agents from the registry
     List,
 
+    /// Search agents and skills by name, description, tags and author
+    Search {
+        /// Search terms; every term must match
+        #[arg(default_value = "")]
+        query: String,
+
+        /// Only show entries whose author contains this
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Only show entries that can be installed to this target (e.g. cursor)
+        #[arg(long, value_name = "TARGET")]
+        target_compatible: Option<String>,
+    },
+
     /// Install an agent configuration
     Install {
         /// Name of the agent to install
         agent: String,
 
-        /// Target editor (claude, cursor)
-        #[arg(short, long, value_enum, default_value = "claude")]
-        target: TargetArg,
+        /// Target editors, comma-separated (claude, cursor, codex, windsurf, copilot,
+        /// gemini, cline, roo, zed, continue, opencode), `all-detected`, or the
+        /// name of an `ax-installer-<name>` plugin.
+        /// Defaults to `default_targets` from ~/.ax/config.toml
+        #[arg(short, long, value_delimiter = ',')]
+        target: Vec<TargetArg>,
 
         /// Install globally (applies to all projects)
         #[arg(short, long, default_value = "false")]
         global: bool,
+
+        /// Never prompt; `${VAR}` secrets that can't be resolved are left as placeholders
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Value for a `${VAR}` placeholder in MCP config (repeatable)
+        #[arg(long = "env", value_name = "NAME=VALUE", value_parser = parse_env_var)]
+        env: Vec<(String, String)>,
+
+        /// Read `${VAR}` values from a .env style file (repeatable)
+        #[arg(long, value_name = "PATH")]
+        env_file: Vec<PathBuf>,
+
+        /// Fail if any `${VAR}` placeholder written as a value (see
+        /// --plaintext-secrets) is left unresolved
+        #[arg(long)]
+        require_env: bool,
+
+        /// Write secret values into MCP configs of editors that can't read
+        /// environment variables (Zed, Cline, plugins) instead of refusing
+        #[arg(long)]
+        plaintext_secrets: bool,
+
+        /// Fail instead of warning when a required tool isn't on PATH
+        #[arg(long)]
+        strict_deps: bool,
+    },
+
+    /// Inspect or clear the registry content cache (~/.ax/cache)
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Upgrade agent.yaml and SKILL.md files to the current schema version
+    Migrate {
+        /// File or directory to migrate
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Report what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Print the JSON Schema for agent.yaml, SKILL.md frontmatter or config.toml
+    Schema {
+        /// Document type
+        #[arg(value_enum)]
+        kind: SchemaKind,
+    },
+
+    /// Show what an agent contains and how each editor installs it
+    Info {
+        /// Agent name, optionally with a version (e.g. rust-architect@^1.0)
+        agent: String,
+
+        /// Also print the files the agent renders to for this target
+        #[arg(short, long)]
+        target: Option<Target>,
+    },
+
+    /// Show an agent's resolved dependency tree
+    Tree {
+        /// Name of the agent to inspect
+        agent: String,
     },
 }
 
-#[derive(Debug, Clone, Copy, ValueEnum)]
+/// Parse a `--env NAME=VALUE` argument
+fn parse_env_var(s: &str) -> Result<(String, String), String> {
+    crate::core::secrets::parse_assignment(s)
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", s))
+}
+
+/// `ax cache` subcommands
+#[derive(Subcommand, Debug, Clone, Copy)]
+pub enum CacheAction {
+    /// List cached documents
+    Ls,
+    /// Remove every cached document
+    Clean,
+    /// Check cached documents against their hashes, dropping corrupted ones
+    Verify,
+}
+
+/// A `--target` value: a built-in editor, `all-detected`, or an installer plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TargetArg {
-    Claude,
-    Cursor,
-    Codex,
+    /// A built-in target (claude, cursor, ...)
+    Builtin(Target),
+    /// Every editor detected on this machine (same detection as `ax init`)
+    AllDetected,
+    /// An external `ax-installer-<name>` plugin
+    Plugin(String),
 }
 
-impl From<TargetArg> for Target {
-    fn from(arg: TargetArg) -> Self {
-        match arg {
-            TargetArg::Claude => Target::Claude,
-            TargetArg::Cursor => Target::Cursor,
-            TargetArg::Codex => Target::Codex,
+impl FromStr for TargetArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err("target name cannot be empty".to_string());
+        }
+        if matches!(s, "all-detected" | "all") {
+            return Ok(TargetArg::AllDetected);
         }
+        Ok(match s.parse::<Target>() {
+            Ok(target) => TargetArg::Builtin(target),
+            Err(_) => TargetArg::Plugin(s.to_string()),
+        })
     }
 }
@@ -2,12 +2,23 @@
 //!
 //! Defines the universal schema for agent.yaml files.
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use anyhow::{Context, Result};
+use semver::{Version, VersionReq};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
 
 /// The main agent configuration matching the universal agent.yaml schema
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AgentConfig {
+    /// Schema version of the document (1 when omitted)
+    #[serde(default = "legacy_schema")]
+    pub schema: u32,
+
     /// Agent name (e.g., "rust-architect")
     pub name: String,
 
@@ -20,7 +31,18 @@ pub struct AgentConfig {
     /// Author or organization
     pub author: String,
 
+    /// Search tags (e.g. "rust", "testing")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Parent agent this one specialises (`name` or `name@range`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<Requirement>,
+
     /// Identity configuration (the brain)
+    ///
+    /// May be omitted by agents that `extends:` another one.
+    #[serde(default)]
     pub identity: Identity,
 
     /// Skills/knowledge base (optional)
@@ -30,10 +52,281 @@ pub struct AgentConfig {
     /// MCP tool configurations (optional)
     #[serde(default)]
     pub mcp: Vec<McpTool>,
+
+    /// Other agents or skills this agent builds on, installed first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<Requirement>,
+
+    /// Per-target overrides keyed by target id (e.g. "cursor")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub targets: BTreeMap<String, TargetOverride>,
+
+    /// Wraps a standalone registry skill: only its skills are installed
+    #[serde(skip)]
+    pub skill_only: bool,
+}
+
+impl AgentConfig {
+    /// Merge this agent over the parent it `extends:`
+    ///
+    /// Skills and MCP tools are merged by name (the child's entry wins, new
+    /// ones are appended); unset identity fields are inherited.
+    pub fn inherit(self, parent: AgentConfig) -> AgentConfig {
+        let mut merged = self;
+        let identity = &mut merged.identity;
+
+        let parent_prompt = parent.identity.system_prompt.trim_end();
+        let child_prompt = identity.system_prompt.trim_end();
+        identity.system_prompt = if child_prompt.is_empty() {
+            parent_prompt.to_string()
+        } else {
+            match identity.prompt_merge {
+                PromptMerge::Append => format!("{}\n\n{}", parent_prompt, child_prompt),
+                PromptMerge::Prepend => format!("{}\n\n{}", child_prompt, parent_prompt),
+                PromptMerge::Replace => child_prompt.to_string(),
+            }
+        };
+        identity.model = identity.model.take().or(parent.identity.model);
+        identity.icon = identity.icon.take().or(parent.identity.icon);
+
+        merged.skills = merge_by_name(parent.skills, merged.skills, |skill| &skill.name);
+        merged.mcp = merge_by_name(parent.mcp, merged.mcp, |tool| &tool.name);
+        merged.requires = merge_by_name(parent.requires, merged.requires, |r| &r.name);
+        if merged.tags.is_empty() {
+            merged.tags = parent.tags;
+        }
+
+        for (target, overrides) in parent.targets {
+            merged.targets.entry(target).or_insert(overrides);
+        }
+
+        merged
+    }
+
+    /// Apply the `targets:` override for a target, if any
+    ///
+    /// The returned config carries the target's frontmatter on its identity
+    /// and skills, ready to be rendered by the installer.
+    pub fn for_target(&self, target: &str) -> Result<AgentConfig> {
+        let mut agent = self.clone();
+        let Some(overrides) = self.targets.get(target) else {
+            return Ok(agent);
+        };
+
+        if let Some(system_prompt) = &overrides.system_prompt {
+            agent.identity.system_prompt = system_prompt.clone();
+        }
+        if let Some(model) = &overrides.model {
+            agent.identity.model = Some(model.clone());
+        }
+        if let Some(icon) = &overrides.icon {
+            agent.identity.icon = Some(icon.clone());
+        }
+
+        if let Some(names) = &overrides.skills {
+            for name in names {
+                if !self.skills.iter().any(|skill| &skill.name == name) {
+                    anyhow::bail!("targets.{}.skills: unknown skill '{}'", target, name);
+                }
+            }
+            agent.skills.retain(|skill| names.contains(&skill.name));
+        }
+
+        if let Some(names) = &overrides.mcp {
+            for name in names {
+                if !self.mcp.iter().any(|tool| &tool.name == name) {
+                    anyhow::bail!("targets.{}.mcp: unknown MCP tool '{}'", target, name);
+                }
+            }
+            agent.mcp.retain(|tool| names.contains(&tool.name));
+        }
+
+        agent.identity.frontmatter = render_frontmatter(&overrides.frontmatter);
+        let skill_frontmatter = render_frontmatter(&overrides.skill_frontmatter);
+        for skill in &mut agent.skills {
+            skill.frontmatter = skill_frontmatter.clone();
+        }
+
+        Ok(agent)
+    }
+}
+
+/// A dependency on another agent or skill, e.g. `shared-rust@^1.2`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    /// Agent or skill name in the registry
+    pub name: String,
+
+    /// Accepted versions (any version when not given)
+    pub version: VersionReq,
+}
+
+impl Requirement {
+    /// Check whether a version string satisfies this requirement
+    pub fn matches(&self, version: &str) -> bool {
+        if self.version == VersionReq::STAR {
+            return true;
+        }
+        Version::parse(version)
+            .map(|version| self.version.matches(&version))
+            .unwrap_or(false)
+    }
+}
+
+impl FromStr for Requirement {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, version) = match s.split_once('@') {
+            Some((name, version)) => (name.trim(), version.trim()),
+            None => (s.trim(), "*"),
+        };
+
+        if name.is_empty() {
+            anyhow::bail!("Invalid requirement '{}': missing name", s);
+        }
+        let version = VersionReq::parse(version)
+            .with_context(|| format!("Invalid version range in requirement '{}'", s))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            version,
+        })
+    }
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.version == VersionReq::STAR {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}@{}", self.name, self.version)
+        }
+    }
+}
+
+/// Documented as either accepted form: `name@range` or `{ name, version }`
+impl JsonSchema for Requirement {
+    fn schema_name() -> String {
+        "Requirement".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        #[allow(dead_code)]
+        #[derive(JsonSchema)]
+        #[serde(untagged)]
+        enum RequirementSpec {
+            /// `name` or `name@range` (e.g. `rust-architect@^1.0`)
+            Short(String),
+            Full {
+                /// Agent or skill name
+                name: String,
+                /// Semver range (any version when omitted)
+                version: Option<String>,
+            },
+        }
+
+        RequirementSpec::json_schema(gen)
+    }
+}
+
+impl Serialize for Requirement {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Accept `name@range` strings or `{ name, version }` maps
+impl<'de> Deserialize<'de> for Requirement {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Short(String),
+            Full { name: String, version: Option<String> },
+        }
+
+        let spec = match Raw::deserialize(deserializer)? {
+            Raw::Short(spec) => spec,
+            Raw::Full { name, version } => match version {
+                Some(version) => format!("{}@{}", name, version),
+                None => name,
+            },
+        };
+        spec.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Merge two lists by name, replacing parent entries the child redefines
+fn merge_by_name<T>(parent: Vec<T>, child: Vec<T>, name: impl Fn(&T) -> &String) -> Vec<T> {
+    let mut merged = parent;
+    for item in child {
+        match merged.iter().position(|existing| name(existing) == name(&item)) {
+            Some(index) => merged[index] = item,
+            None => merged.push(item),
+        }
+    }
+    merged
+}
+
+/// Overrides applied when installing to one target
+///
+/// Unset fields keep the agent's defaults; `skills` and `mcp` list the
+/// names to include for this target.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TargetOverride {
+    /// Replacement system prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+
+    /// Replacement model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Replacement icon
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
+    /// Skills to install (all when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills: Option<Vec<String>>,
+
+    /// MCP tools to configure (all when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp: Option<Vec<String>>,
+
+    /// Extra frontmatter for the identity file (e.g. Claude's `tools`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub frontmatter: BTreeMap<String, serde_yaml::Value>,
+
+    /// Extra frontmatter for every skill file (e.g. Cursor's `globs`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub skill_frontmatter: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Render frontmatter values as single-line YAML (collections in flow style)
+fn render_frontmatter(values: &BTreeMap<String, serde_yaml::Value>) -> Vec<(String, String)> {
+    values
+        .iter()
+        .map(|(key, value)| {
+            let rendered = match value {
+                serde_yaml::Value::String(s) => s.clone(),
+                other => serde_json::to_string(other).unwrap_or_default(),
+            };
+            (key.clone(), rendered)
+        })
+        .collect()
+}
+
+fn legacy_schema() -> u32 {
+    super::schema::LEGACY_SCHEMA
 }
 
 /// Identity configuration - becomes the system prompt
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Identity {
     /// Preferred model (e.g., "claude-3-5-sonnet-latest")
     #[serde(default)]
@@ -44,13 +337,45 @@ pub struct Identity {
     pub icon: Option<String>,
 
     /// The system prompt that defines the agent's behavior
+    #[serde(default)]
     pub system_prompt: String,
+
+    /// How `system_prompt` combines with the parent's when using `extends:`
+    #[serde(default, skip_serializing_if = "PromptMerge::is_default")]
+    pub prompt_merge: PromptMerge,
+
+    /// Extra frontmatter for the current install target (from `targets:`)
+    #[serde(skip)]
+    pub frontmatter: Vec<(String, String)>,
+}
+
+/// How a child agent's system prompt combines with its parent's
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptMerge {
+    /// Parent prompt followed by the child's
+    #[default]
+    Append,
+    /// Child prompt followed by the parent's
+    Prepend,
+    /// Child prompt only
+    Replace,
+}
+
+impl PromptMerge {
+    fn is_default(&self) -> bool {
+        *self == PromptMerge::default()
+    }
 }
 
 /// Skill definition - follows Agent Skills standard (agentskills.io)
 /// Can be loaded from SKILL.md files with YAML frontmatter
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Skill {
+    /// Schema version of a standalone SKILL.md (1 when omitted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<u32>,
+
     /// Skill name (must match directory name, lowercase with hyphens)
     pub name: String,
 
@@ -82,6 +407,19 @@ pub struct Skill {
     #[serde(default)]
     pub dependencies: Option<String>,
 
+    /// File globs the skill applies to (a list or a comma-separated string)
+    #[serde(
+        default,
+        deserialize_with = "crate::core::json_schema::string_or_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    #[schemars(with = "crate::core::json_schema::StringOrList")]
+    pub globs: Vec<String>,
+
+    /// When editors with rule activation modes should load the skill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activation: Option<Activation>,
+
     /// Path to source directory (for copying scripts/references/assets)
     #[serde(skip)]
     pub source_dir: Option<std::path::PathBuf>,
@@ -89,15 +427,56 @@ pub struct Skill {
     /// Remote base URL for fetching subdirectories (scripts/references/assets)
     #[serde(skip)]
     pub remote_base_url: Option<String>,
+
+    /// Extra frontmatter for the current install target (from `targets:`)
+    #[serde(skip)]
+    pub frontmatter: Vec<(String, String)>,
+}
+
+impl Skill {
+    /// Effective activation mode
+    ///
+    /// Without an explicit `activation`, skills with `globs` attach to
+    /// matching files and the rest are always applied. A `description`
+    /// alone doesn't change the mode, so existing rules stay always-on.
+    pub fn activation(&self) -> Activation {
+        if let Some(activation) = self.activation {
+            return activation;
+        }
+        if !self.globs.is_empty() {
+            Activation::Auto
+        } else {
+            Activation::Always
+        }
+    }
+}
+
+/// How a skill is activated in editors that support rule modes (e.g. Cursor)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Activation {
+    /// Included in every request
+    Always,
+    /// Attached when a file matching `globs` is referenced
+    Auto,
+    /// Included when the agent decides it's relevant from the `description`
+    AgentRequested,
+    /// Only included when explicitly mentioned
+    Manual,
 }
 
 /// MCP Tool configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct McpTool {
     /// Tool name
     pub name: String,
 
-    /// Command to execute
+    /// How the server is reached (stdio when omitted)
+    #[serde(default, skip_serializing_if = "McpTransport::is_stdio")]
+    pub transport: McpTransport,
+
+    /// Command to execute (stdio servers)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub command: String,
 
     /// Command arguments
@@ -108,11 +487,92 @@ pub struct McpTool {
     #[serde(default)]
     pub env: HashMap<String, String>,
 
+    /// Server URL (http and sse servers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// HTTP headers sent to the server (http and sse servers)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+
     /// Optional URL for setup instructions (e.g. API key generation)
     #[serde(default)]
     pub setup_url: Option<String>,
 }
 
+impl McpTool {
+    /// Check that exactly the fields of the tool's transport are set
+    pub fn validate(&self) -> Result<()> {
+        match self.transport {
+            McpTransport::Stdio => {
+                if self.command.trim().is_empty() {
+                    anyhow::bail!("MCP tool '{}' needs a `command` for stdio transport", self.name);
+                }
+                if self.url.is_some() || !self.headers.is_empty() {
+                    anyhow::bail!(
+                        "MCP tool '{}' sets `url`/`headers`, which need `transport: http` or `sse`",
+                        self.name
+                    );
+                }
+            }
+            McpTransport::Http | McpTransport::Sse => {
+                let url = self.url.as_deref().unwrap_or_default();
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    anyhow::bail!(
+                        "MCP tool '{}' needs an http(s) `url` for {} transport",
+                        self.name,
+                        self.transport
+                    );
+                }
+                if !self.command.is_empty() || !self.args.is_empty() || !self.env.is_empty() {
+                    anyhow::bail!(
+                        "MCP tool '{}' uses {} transport and can't set `command`, `args` or `env` \
+                         (use `headers` for credentials)",
+                        self.name,
+                        self.transport
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The server URL of a remote (http or sse) tool
+    pub fn remote_url(&self) -> &str {
+        self.url.as_deref().unwrap_or_default()
+    }
+}
+
+/// Transport used to talk to an MCP server
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransport {
+    /// Local process speaking over stdin/stdout
+    #[default]
+    Stdio,
+    /// Remote server using streamable HTTP
+    Http,
+    /// Remote server using server-sent events (legacy)
+    Sse,
+}
+
+impl McpTransport {
+    fn is_stdio(&self) -> bool {
+        *self == McpTransport::Stdio
+    }
+}
+
+impl fmt::Display for McpTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            McpTransport::Stdio => "stdio",
+            McpTransport::Http => "http",
+            McpTransport::Sse => "sse",
+        };
+        f.write_str(name)
+    }
+}
+
 /// Minimal agent info for registry listing
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentInfo {
@@ -120,19 +580,76 @@ pub struct AgentInfo {
     pub version: String,
     pub description: String,
     pub author: String,
+
+    /// Whether the entry is a full agent or a standalone skill
+    #[serde(default)]
+    pub kind: EntryKind,
+
+    /// Search tags shown in results
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Extra search terms (skill and MCP tool names, synonyms)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+
+    /// Target ids the entry is known to work with (all when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
+
+    /// Published versions, newest first, when the registry lists them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<String>,
+}
+
+impl AgentInfo {
+    /// Whether the entry can be installed to a target
+    pub fn supports_target(&self, target: &str) -> bool {
+        self.targets.is_empty() || self.targets.iter().any(|t| t.eq_ignore_ascii_case(target))
+    }
 }
 
 impl From<&AgentConfig> for AgentInfo {
     fn from(config: &AgentConfig) -> Self {
+        let keywords = config
+            .skills
+            .iter()
+            .map(|skill| skill.name.clone())
+            .chain(config.mcp.iter().map(|tool| tool.name.clone()))
+            .collect();
+
         Self {
             name: config.name.clone(),
             version: config.version.clone(),
             description: config.description.clone(),
             author: config.author.clone(),
+            kind: EntryKind::Agent,
+            tags: config.tags.clone(),
+            keywords,
+            targets: Vec::new(),
+            versions: Vec::new(),
         }
     }
 }
 
+/// Kind of registry entry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    #[default]
+    Agent,
+    Skill,
+}
+
+impl fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            EntryKind::Agent => "agent",
+            EntryKind::Skill => "skill",
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +685,182 @@ mcp:
         assert_eq!(agent.skills.len(), 1);
         assert_eq!(agent.mcp.len(), 1);
     }
+
+    #[test]
+    fn test_skill_activation() {
+        let yaml = r#"
+- name: "always"
+- name: "described"
+  description: "Use when writing tests"
+- name: "auto"
+  description: "Rust rules"
+  globs: "**/*.rs, Cargo.toml"
+- name: "manual"
+  description: "Release checklist"
+  activation: manual
+- name: "requested"
+  description: "Use when writing tests"
+  activation: agent-requested
+"#;
+
+        let skills: Vec<Skill> = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(skills[0].activation(), Activation::Always);
+        // A description alone keeps the skill always-on
+        assert_eq!(skills[1].activation(), Activation::Always);
+        assert_eq!(skills[2].activation(), Activation::Auto);
+        assert_eq!(skills[2].globs, vec!["**/*.rs", "Cargo.toml"]);
+        assert_eq!(skills[3].activation(), Activation::Manual);
+        assert_eq!(skills[4].activation(), Activation::AgentRequested);
+    }
+
+    #[test]
+    fn test_inherit_from_parent() {
+        let parent: AgentConfig = serde_yaml::from_str(
+            r#"
+name: "code-cleaner"
+version: "1.2.0"
+description: "Cleans code"
+author: "a"
+identity:
+  icon: "🧹"
+  system_prompt: "Clean the code."
+skills:
+  - name: "solid"
+    content: "Parent SOLID"
+  - name: "naming"
+    content: "Parent naming"
+mcp:
+  - name: "fs"
+    command: "fs-server"
+"#,
+        )
+        .unwrap();
+
+        let child: AgentConfig = serde_yaml::from_str(
+            r#"
+name: "fastapi-code-cleaner"
+version: "0.1.0"
+description: "Cleans FastAPI code"
+author: "a"
+extends: "code-cleaner@^1"
+identity:
+  system_prompt: "Focus on FastAPI."
+skills:
+  - name: "naming"
+    content: "Child naming"
+  - name: "fastapi"
+    content: "FastAPI rules"
+"#,
+        )
+        .unwrap();
+
+        let merged = child.inherit(parent.clone());
+        assert_eq!(merged.name, "fastapi-code-cleaner");
+        assert_eq!(merged.identity.system_prompt, "Clean the code.\n\nFocus on FastAPI.");
+        assert_eq!(merged.identity.icon.as_deref(), Some("🧹"));
+        let skills: Vec<(&str, &str)> = merged
+            .skills
+            .iter()
+            .map(|s| (s.name.as_str(), s.content.as_str()))
+            .collect();
+        assert_eq!(
+            skills,
+            vec![
+                ("solid", "Parent SOLID"),
+                ("naming", "Child naming"),
+                ("fastapi", "FastAPI rules")
+            ]
+        );
+        assert_eq!(merged.mcp.len(), 1);
+
+        let mut replacing = merged.clone();
+        replacing.identity.system_prompt = "Only FastAPI.".to_string();
+        replacing.identity.prompt_merge = PromptMerge::Replace;
+        let merged = replacing.inherit(parent);
+        assert_eq!(merged.identity.system_prompt, "Only FastAPI.");
+    }
+
+    #[test]
+    fn test_mcp_transport_validation() {
+        let tools: Vec<McpTool> = serde_yaml::from_str(
+            r#"
+- name: "local"
+  command: "npx"
+- name: "remote"
+  transport: "http"
+  url: "https://mcp.example.com/mcp"
+  headers:
+    Authorization: "Bearer ${TOKEN}"
+- name: "no-url"
+  transport: "sse"
+- name: "mixed"
+  command: "npx"
+  url: "https://mcp.example.com/sse"
+"#,
+        )
+        .unwrap();
+
+        assert!(tools[0].validate().is_ok());
+        assert!(tools[1].validate().is_ok());
+        assert!(tools[2].validate().is_err());
+        assert!(tools[3].validate().is_err());
+    }
+
+    #[test]
+    fn test_target_overrides() {
+        let yaml = r#"
+name: "test-agent"
+version: "1.0.0"
+description: "A test agent"
+author: "test-author"
+identity:
+  model: "sonnet"
+  system_prompt: "Default prompt"
+skills:
+  - name: "one"
+  - name: "two"
+mcp:
+  - name: "fs"
+    command: "echo"
+targets:
+  cursor:
+    skills: ["two"]
+    mcp: []
+    skill_frontmatter:
+      globs: "**/*.rs"
+      alwaysApply: false
+  claude:
+    model: "opus"
+    frontmatter:
+      tools: "Read, Grep"
+"#;
+
+        let agent: AgentConfig = serde_yaml::from_str(yaml).unwrap();
+
+        let cursor = agent.for_target("cursor").unwrap();
+        assert_eq!(cursor.identity.system_prompt, "Default prompt");
+        assert_eq!(cursor.skills.len(), 1);
+        assert_eq!(cursor.skills[0].name, "two");
+        assert_eq!(
+            cursor.skills[0].frontmatter,
+            vec![
+                ("alwaysApply".to_string(), "false".to_string()),
+                ("globs".to_string(), "**/*.rs".to_string()),
+            ]
+        );
+        assert!(cursor.mcp.is_empty());
+
+        let claude = agent.for_target("claude").unwrap();
+        assert_eq!(claude.identity.model.as_deref(), Some("opus"));
+        assert_eq!(claude.skills.len(), 2);
+        assert_eq!(claude.mcp.len(), 1);
+
+        let codex = agent.for_target("codex").unwrap();
+        assert_eq!(codex.identity.model.as_deref(), Some("sonnet"));
+        assert!(codex.identity.frontmatter.is_empty());
+
+        let mut bad = agent.clone();
+        bad.targets.get_mut("cursor").unwrap().skills = Some(vec!["missing".to_string()]);
+        assert!(bad.for_target("cursor").is_err());
+    }
 }
@@ -0,0 +1,82 @@
+//! Bundled Registry
+//!
+//! A small set of example agents compiled into ax from `examples/agents/`.
+//! They are only used when `registry_url = "bundled"` is configured, e.g. to
+//! try ax without network access; every use prints a warning, so a broken
+//! registry URL is never mistaken for a working one.
+
+use anyhow::{Context, Result};
+use std::sync::Once;
+
+use super::agent::{AgentConfig, AgentInfo};
+use super::schema;
+use crate::utils::ui;
+
+/// `registry_url` value that selects the bundled registry
+pub const BUNDLED: &str = "bundled";
+
+/// Agent definitions shipped with ax, keyed by name
+const AGENTS: &[(&str, &str)] = &[
+    (
+        "rust-architect",
+        include_str!("../../examples/agents/rust-architect.yaml"),
+    ),
+    (
+        "fullstack-next",
+        include_str!("../../examples/agents/fullstack-next.yaml"),
+    ),
+    (
+        "qa-testing-squad",
+        include_str!("../../examples/agents/qa-testing-squad.yaml"),
+    ),
+];
+
+/// Whether a registry URL selects the bundled registry
+pub fn is_bundled(registry_url: &str) -> bool {
+    registry_url.trim() == BUNDLED
+}
+
+/// Index of the bundled agents
+pub fn agents() -> Result<Vec<AgentInfo>> {
+    warn_once();
+    AGENTS
+        .iter()
+        .map(|(name, yaml)| parse(name, yaml).map(|agent| AgentInfo::from(&agent)))
+        .collect()
+}
+
+/// A bundled agent by name
+pub fn agent(name: &str) -> Option<Result<AgentConfig>> {
+    warn_once();
+    AGENTS
+        .iter()
+        .find(|(bundled, _)| *bundled == name)
+        .map(|(name, yaml)| parse(name, yaml))
+}
+
+fn parse(name: &str, yaml: &str) -> Result<AgentConfig> {
+    schema::parse_agent(yaml).with_context(|| format!("Failed to parse bundled agent '{}'", name))
+}
+
+fn warn_once() {
+    static WARNED: Once = Once::new();
+    WARNED.call_once(|| {
+        ui::print_warning(
+            "Using the example agents bundled with ax (registry_url = \"bundled\"), not a live registry",
+        );
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_agents_parse() {
+        for (name, yaml) in AGENTS {
+            let agent = parse(name, yaml).unwrap();
+            assert_eq!(&agent.name, name);
+            assert!(!agent.identity.system_prompt.is_empty());
+        }
+    }
+}
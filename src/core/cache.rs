@@ -0,0 +1,327 @@
+//! Content Cache
+//!
+//! Registry documents (registry.json, agent YAML, SKILL.md) and skill files
+//! are cached under ~/.ax/cache, keyed by the SHA-256 of their URL. Each
+//! entry records the SHA-256 of its body and the `ETag`/`Last-Modified`
+//! validators, so later requests are conditional and `--offline` can work
+//! from the cache alone.
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::error::AxError;
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Serve every request from the cache, never touching the network
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Whether `--offline` was requested
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Metadata stored next to a cached body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub url: String,
+    /// SHA-256 of the body, hex encoded
+    pub sha256: String,
+    pub size: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    /// When the body was last fetched or revalidated (Unix seconds)
+    pub fetched_at: u64,
+}
+
+/// The on-disk cache
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    /// Open ~/.ax/cache
+    pub fn open() -> Result<Self> {
+        Ok(Self::at(crate::utils::paths::ax_cache_dir()?))
+    }
+
+    /// Open a cache rooted at `dir`
+    pub fn at(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Look up a URL, ignoring entries whose body no longer matches its hash
+    pub fn get(&self, url: &str) -> Option<(CacheEntry, Vec<u8>)> {
+        let key = key(url);
+        let entry = self.read_entry(&key).ok()?;
+        let body = fs::read(self.body_path(&key)).ok()?;
+        (entry.url == url && digest(&body) == entry.sha256).then_some((entry, body))
+    }
+
+    /// Store a response body with its validators
+    pub fn put(
+        &self,
+        url: &str,
+        body: &[u8],
+        etag: Option<String>,
+        last_modified: Option<String>,
+    ) -> Result<CacheEntry> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+
+        let key = key(url);
+        let entry = CacheEntry {
+            url: url.to_string(),
+            sha256: digest(body),
+            size: body.len() as u64,
+            etag,
+            last_modified,
+            fetched_at: now(),
+        };
+        fs::write(self.body_path(&key), body)?;
+        fs::write(self.meta_path(&key), serde_json::to_string_pretty(&entry)?)?;
+        Ok(entry)
+    }
+
+    /// Mark a cached entry as freshly revalidated
+    pub fn touch(&self, url: &str) -> Result<()> {
+        let key = key(url);
+        let mut entry = self.read_entry(&key)?;
+        entry.fetched_at = now();
+        fs::write(self.meta_path(&key), serde_json::to_string_pretty(&entry)?)?;
+        Ok(())
+    }
+
+    /// Drop a URL from the cache
+    pub fn remove(&self, url: &str) -> Result<()> {
+        let key = key(url);
+        for path in [self.body_path(&key), self.meta_path(&key)] {
+            if path.exists() {
+                fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Every cached entry, sorted by URL
+    pub fn entries(&self) -> Result<Vec<CacheEntry>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for file in fs::read_dir(&self.dir)? {
+            let path = file?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(key) = path.file_stem().and_then(|stem| stem.to_str()) {
+                if let Ok(entry) = self.read_entry(key) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        entries.sort_by(|a, b| a.url.cmp(&b.url));
+        Ok(entries)
+    }
+
+    /// Check every entry's body against its hash, returning the broken ones
+    pub fn verify(&self) -> Result<Vec<CacheEntry>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .filter(|entry| self.get(&entry.url).is_none())
+            .collect())
+    }
+
+    /// Remove everything, returning the number of entries removed
+    pub fn clean(&self) -> Result<usize> {
+        let count = self.entries()?.len();
+        if self.dir.exists() {
+            fs::remove_dir_all(&self.dir)
+                .with_context(|| format!("Failed to remove {}", self.dir.display()))?;
+        }
+        Ok(count)
+    }
+
+    fn read_entry(&self, key: &str) -> Result<CacheEntry> {
+        let content = fs::read_to_string(self.meta_path(key))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn body_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.body", key))
+    }
+
+    fn meta_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+/// GET a URL through the cache, returning `None` if it doesn't exist
+pub async fn fetch(client: &reqwest::Client, url: &str) -> Result<Option<Vec<u8>>> {
+    let cache = Cache::open()?;
+    let cached = cache.get(url);
+    if is_offline() {
+        return Ok(cached.map(|(_, body)| body));
+    }
+
+    let response = client
+        .get(url)
+        .headers(conditional_headers(cached.as_ref().map(|(entry, _)| entry)))
+        .send()
+        .await
+        .map_err(|err| registry_error(url, err))?;
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = if status.is_success() {
+        response.bytes().await.map_err(|err| registry_error(url, err))?.to_vec()
+    } else {
+        Vec::new()
+    };
+
+    store(&cache, url, status, &headers, body, cached)
+}
+
+/// Blocking variant of [`fetch`] for installers
+pub fn fetch_blocking(url: &str) -> Result<Option<Vec<u8>>> {
+    let cache = Cache::open()?;
+    let cached = cache.get(url);
+    if is_offline() {
+        return Ok(cached.map(|(_, body)| body));
+    }
+
+    let response = reqwest::blocking::Client::new()
+        .get(url)
+        .headers(conditional_headers(cached.as_ref().map(|(entry, _)| entry)))
+        .send()
+        .map_err(|err| registry_error(url, err))?;
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = if status.is_success() {
+        response.bytes().map_err(|err| registry_error(url, err))?.to_vec()
+    } else {
+        Vec::new()
+    };
+
+    store(&cache, url, status, &headers, body, cached)
+}
+
+/// Revalidation headers for a cached entry
+fn conditional_headers(entry: Option<&CacheEntry>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let Some(entry) = entry else {
+        return headers;
+    };
+
+    if let Some(etag) = entry.etag.as_deref().and_then(|v| v.parse().ok()) {
+        headers.insert(IF_NONE_MATCH, etag);
+    }
+    if let Some(modified) = entry.last_modified.as_deref().and_then(|v| v.parse().ok()) {
+        headers.insert(IF_MODIFIED_SINCE, modified);
+    }
+    headers
+}
+
+/// Update the cache from a response and return the body to use
+fn store(
+    cache: &Cache,
+    url: &str,
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: Vec<u8>,
+    cached: Option<(CacheEntry, Vec<u8>)>,
+) -> Result<Option<Vec<u8>>> {
+    if status == StatusCode::NOT_MODIFIED {
+        if let Some((_, body)) = cached {
+            cache.touch(url).ok();
+            return Ok(Some(body));
+        }
+    }
+
+    if status == StatusCode::NOT_FOUND {
+        cache.remove(url).ok();
+        return Ok(None);
+    }
+
+    if !status.is_success() {
+        return Err(AxError::Registry {
+            url: url.to_string(),
+            reason: format!("HTTP {}", status),
+        }
+        .into());
+    }
+
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|value: &reqwest::header::HeaderValue| value.to_str().ok())
+            .map(str::to_string)
+    };
+    // A cache that can't be written shouldn't fail the request
+    cache.put(url, &body, header(ETAG), header(LAST_MODIFIED)).ok();
+    Ok(Some(body))
+}
+
+fn registry_error(url: &str, err: reqwest::Error) -> AxError {
+    AxError::Registry {
+        url: url.to_string(),
+        reason: err.to_string(),
+    }
+}
+
+fn key(url: &str) -> String {
+    digest(url.as_bytes())
+}
+
+fn digest(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_roundtrip_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::at(dir.path());
+        let url = "https://example.com/agents/app.yaml";
+
+        cache.put(url, b"name: app", Some("\"v1\"".into()), None).unwrap();
+        let (entry, body) = cache.get(url).unwrap();
+        assert_eq!(body, b"name: app");
+        assert_eq!(entry.etag.as_deref(), Some("\"v1\""));
+        assert!(cache.verify().unwrap().is_empty());
+
+        // A corrupted body is ignored and reported
+        fs::write(cache.body_path(&key(url)), "tampered").unwrap();
+        assert!(cache.get(url).is_none());
+        assert_eq!(cache.verify().unwrap().len(), 1);
+
+        assert_eq!(cache.clean().unwrap(), 1);
+        assert!(cache.entries().unwrap().is_empty());
+    }
+}
@@ -2,23 +2,56 @@
 //!
 //! Manages the APM configuration file at ~/.apm/config.toml
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// APM Configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ApmConfig {
-    /// Default target for installations (claude, cursor)
-    pub default_target: String,
+    /// Default targets for installations (e.g. ["claude", "cursor"] or ["all-detected"])
+    ///
+    /// Older configs with a single `default_target = "claude"` string are still accepted.
+    #[serde(alias = "default_target", deserialize_with = "crate::core::json_schema::string_or_list")]
+    #[schemars(with = "crate::core::json_schema::StringOrList")]
+    pub default_targets: Vec<String>,
 
-    /// Registry URL (defaults to GitHub)
+    /// Registry URL (defaults to GitHub), or "bundled" for the example agents shipped with ax
     #[serde(default = "default_registry_url")]
     pub registry_url: String,
 
     /// Whether to show verbose output
     #[serde(default)]
     pub verbose: bool,
+
+    /// Template variables available to every agent (e.g. `{company}`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+}
+
+/// Project configuration read from ax.toml in the project root
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    /// Template variables for this project, overriding the global ones
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+}
+
+impl ProjectConfig {
+    /// Load ax.toml from the current directory, if present
+    pub fn load_or_default() -> Result<Self> {
+        let path = crate::utils::paths::project_config_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let config: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(config)
+    }
 }
 
 fn default_registry_url() -> String {
@@ -27,18 +60,20 @@ fn default_registry_url() -> String {
 
 impl ApmConfig {
     /// Create a new configuration with default settings
-    pub fn new(default_target: String) -> Self {
+    pub fn new(default_targets: Vec<String>) -> Self {
         Self {
-            default_target,
+            default_targets,
             registry_url: default_registry_url(),
             verbose: false,
+            variables: BTreeMap::new(),
         }
     }
 
     /// Load configuration from a file
     pub fn load(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
-        let config: Self = toml::from_str(&content)?;
+        let config: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
         Ok(config)
     }
 
@@ -55,13 +90,27 @@ impl ApmConfig {
         if path.exists() {
             Self::load(&path)
         } else {
-            Ok(Self::new("claude".to_string()))
+            Ok(Self::default())
         }
     }
 }
 
 impl Default for ApmConfig {
     fn default() -> Self {
-        Self::new("claude".to_string())
+        Self::new(vec!["claude".to_string()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_default_target() {
+        let config: ApmConfig = toml::from_str(r#"default_target = "cursor""#).unwrap();
+        assert_eq!(config.default_targets, vec!["cursor"]);
+
+        let config: ApmConfig = toml::from_str(r#"default_targets = ["claude", "cursor"]"#).unwrap();
+        assert_eq!(config.default_targets, vec!["claude", "cursor"]);
     }
 }
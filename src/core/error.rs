@@ -0,0 +1,149 @@
+//! Error Types
+//!
+//! Failures that callers may want to tell apart. Commands still return
+//! `anyhow::Result`; an `AxError` somewhere in the error chain decides the
+//! process exit code, so wrappers can react to specific failures.
+
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Exit codes, documented in the README
+pub mod exit_code {
+    /// Any failure not covered below
+    pub const FAILURE: i32 = 1;
+    /// Invalid command-line usage (reported by clap)
+    pub const USAGE: i32 = 2;
+    /// The registry couldn't be reached or returned an error
+    pub const REGISTRY: i32 = 3;
+    /// The agent or skill doesn't exist in the registry (or offline cache)
+    pub const NOT_FOUND: i32 = 4;
+    /// An agent.yaml, SKILL.md or registry index couldn't be parsed
+    pub const PARSE: i32 = 5;
+    /// Dependencies or `extends:` couldn't be resolved
+    pub const RESOLUTION: i32 = 6;
+    /// The agent definition or install inputs are invalid
+    pub const VALIDATION: i32 = 7;
+    /// Installing into one or more targets failed
+    pub const INSTALL: i32 = 8;
+    /// A file couldn't be read or written
+    pub const IO: i32 = 9;
+    /// A file couldn't be read or written due to permissions
+    pub const PERMISSION_DENIED: i32 = 10;
+}
+
+/// A categorised ax failure
+#[derive(Debug, Error)]
+pub enum AxError {
+    /// The registry couldn't be reached or answered with an error
+    #[error("Failed to reach registry at {url}: {reason}")]
+    Registry { url: String, reason: String },
+
+    /// No agent or skill with this name exists in the registry
+    #[error("Agent or skill '{0}' not found in registry")]
+    NotFound(String),
+
+    /// `--offline` was given but the agent or document isn't cached
+    #[error("'{0}' is not in the offline cache; run once without --offline to cache it")]
+    NotCached(String),
+
+    /// A document couldn't be parsed; the cause is the underlying error
+    #[error("Failed to parse {0}")]
+    Parse(String),
+
+    /// A dependency cycle, version conflict or broken `extends:` chain
+    #[error("{0}")]
+    Resolution(String),
+
+    /// An invalid agent definition or missing install input
+    #[error("{0}")]
+    Validation(String),
+
+    /// A target's installer failed; the cause is the underlying error
+    #[error("{0} installer failed")]
+    Installer(String),
+
+    /// Installing failed for some of the requested targets
+    #[error("Failed to install {agent} to {failed} target(s)")]
+    InstallFailed { agent: String, failed: usize },
+
+    /// A file couldn't be written
+    #[error("Failed to write {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+impl AxError {
+    /// Process exit code for this failure
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AxError::Registry { .. } => exit_code::REGISTRY,
+            AxError::NotFound(_) | AxError::NotCached(_) => exit_code::NOT_FOUND,
+            AxError::Parse(_) => exit_code::PARSE,
+            AxError::Resolution(_) => exit_code::RESOLUTION,
+            AxError::Validation(_) => exit_code::VALIDATION,
+            AxError::Installer(_) | AxError::InstallFailed { .. } => exit_code::INSTALL,
+            AxError::Io { source, .. } => io_exit_code(source),
+        }
+    }
+}
+
+/// Exit code for an error, from the most specific known cause in its chain
+///
+/// Permission problems win over everything else, since they're the most
+/// actionable; then the outermost `AxError`; then IO and network errors.
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    let io = err.chain().find_map(|cause| cause.downcast_ref::<std::io::Error>());
+    if let Some(io) = io {
+        if io.kind() == std::io::ErrorKind::PermissionDenied {
+            return exit_code::PERMISSION_DENIED;
+        }
+    }
+
+    if let Some(ax) = err.downcast_ref::<AxError>() {
+        return ax.exit_code();
+    }
+    if let Some(io) = io {
+        return io_exit_code(io);
+    }
+    if err.chain().any(|cause| cause.is::<reqwest::Error>()) {
+        return exit_code::REGISTRY;
+    }
+
+    exit_code::FAILURE
+}
+
+fn io_exit_code(err: &std::io::Error) -> i32 {
+    if err.kind() == std::io::ErrorKind::PermissionDenied {
+        exit_code::PERMISSION_DENIED
+    } else {
+        exit_code::IO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_exit_codes() {
+        let err = anyhow::Error::from(AxError::NotFound("nope".into())).context("Fetching agent");
+        assert_eq!(exit_code(&err), exit_code::NOT_FOUND);
+
+        let parse = serde_yaml::from_str::<u32>("[").unwrap_err();
+        let err = Err::<(), _>(parse)
+            .context(AxError::Parse("agent 'x'".into()))
+            .unwrap_err();
+        assert_eq!(exit_code(&err), exit_code::PARSE);
+        assert!(format!("{:#}", err).starts_with("Failed to parse agent 'x': "));
+
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        let err = anyhow::Error::from(denied).context(AxError::Installer("Cursor".into()));
+        assert_eq!(exit_code(&err), exit_code::PERMISSION_DENIED);
+
+        assert_eq!(exit_code(&anyhow::anyhow!("boom")), exit_code::FAILURE);
+    }
+}
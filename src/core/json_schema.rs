@@ -0,0 +1,91 @@
+//! JSON Schemas
+//!
+//! Generates JSON Schemas for agent.yaml, SKILL.md frontmatter and
+//! config.toml from the Rust types, so editors can validate and complete
+//! registry content. The published copies live in `schemas/`.
+
+use schemars::schema::RootSchema;
+use schemars::{schema_for, JsonSchema};
+use serde::{Deserialize, Deserializer};
+
+use super::agent::{AgentConfig, Skill};
+use super::config::ApmConfig;
+
+/// A document type with a published schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SchemaKind {
+    /// agent.yaml
+    Agent,
+    /// SKILL.md frontmatter
+    Skill,
+    /// ~/.ax/config.toml
+    Config,
+}
+
+impl SchemaKind {
+    /// Every published schema
+    pub const ALL: [SchemaKind; 3] = [SchemaKind::Agent, SchemaKind::Skill, SchemaKind::Config];
+
+    /// File name of the published schema in `schemas/`
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            SchemaKind::Agent => "agent.schema.json",
+            SchemaKind::Skill => "skill.schema.json",
+            SchemaKind::Config => "config.schema.json",
+        }
+    }
+}
+
+/// Generate the pretty-printed JSON Schema for a document type
+pub fn generate(kind: SchemaKind) -> String {
+    let schema: RootSchema = match kind {
+        SchemaKind::Agent => schema_for!(AgentConfig),
+        SchemaKind::Skill => schema_for!(Skill),
+        SchemaKind::Config => schema_for!(ApmConfig),
+    };
+
+    let mut json = serde_json::to_string_pretty(&schema).expect("schemas serialize to JSON");
+    json.push('\n');
+    json
+}
+
+/// Either a single (comma-separated) string or a list of strings
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub(crate) enum StringOrList {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Deserialize a [`StringOrList`] field, splitting a single string on commas
+/// and dropping empty entries
+pub(crate) fn string_or_list<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = match StringOrList::deserialize(deserializer)? {
+        StringOrList::One(value) => value.split(',').map(|v| v.trim().to_string()).collect(),
+        StringOrList::Many(values) => values,
+    };
+
+    Ok(values.into_iter().filter(|v| !v.is_empty()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Regenerate with `ax schema <kind> > schemas/<kind>.schema.json`
+    #[test]
+    fn test_published_schemas_in_sync() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("schemas");
+        for kind in SchemaKind::ALL {
+            let published = std::fs::read_to_string(dir.join(kind.file_name())).unwrap_or_default();
+            assert!(
+                published == generate(kind),
+                "schemas/{} is out of date with the Rust types",
+                kind.file_name()
+            );
+        }
+    }
+}
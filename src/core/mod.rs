@@ -1,5 +1,14 @@
 //! Core Module - Data models and business logic
 
 pub mod agent;
+pub mod bundled;
+pub mod cache;
 pub mod config;
+pub mod error;
+pub mod json_schema;
 pub mod registry;
+pub mod resolver;
+pub mod schema;
+pub mod search;
+pub mod secrets;
+pub mod template;
@@ -0,0 +1,208 @@
+//! Dependency Resolver
+//!
+//! Builds the graph of agents and skills reachable through `requires:`,
+//! rejects cycles and version conflicts, and orders the graph so that
+//! dependencies are installed before the agents that need them.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, VecDeque};
+
+use super::agent::AgentConfig;
+use super::error::AxError;
+use super::registry::Registry;
+
+/// A resolved dependency graph
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    /// Agents in install order: dependencies first, the root agent last
+    pub order: Vec<AgentConfig>,
+}
+
+impl Resolution {
+    /// The agent that was resolved
+    pub fn root(&self) -> &AgentConfig {
+        self.order.last().expect("a resolution always contains its root")
+    }
+
+    /// Everything the root agent depends on, in install order
+    pub fn dependencies(&self) -> &[AgentConfig] {
+        &self.order[..self.order.len() - 1]
+    }
+
+    /// Look up a resolved agent by name
+    pub fn get(&self, name: &str) -> Option<&AgentConfig> {
+        self.order.iter().find(|agent| agent.name == name)
+    }
+}
+
+/// Fetch every agent reachable from `root` and resolve the graph
+pub async fn resolve(registry: &Registry, root: AgentConfig) -> Result<Resolution> {
+    let root_name = root.name.clone();
+    let mut queue: VecDeque<String> = root.requires.iter().map(|r| r.name.clone()).collect();
+
+    let mut agents = BTreeMap::new();
+    agents.insert(root.name.clone(), root);
+
+    while let Some(name) = queue.pop_front() {
+        if agents.contains_key(&name) {
+            continue;
+        }
+
+        let agent = registry
+            .fetch_agent(&name)
+            .await
+            .with_context(|| format!("Failed to fetch dependency '{}'", name))?;
+        queue.extend(agent.requires.iter().map(|r| r.name.clone()));
+        agents.insert(name, agent);
+    }
+
+    resolve_graph(&root_name, agents)
+}
+
+/// Resolve an already fetched set of agents, keyed by name
+pub fn resolve_graph(root: &str, mut agents: BTreeMap<String, AgentConfig>) -> Result<Resolution> {
+    check_versions(&agents)?;
+
+    let mut path = Vec::new();
+    let mut done = Vec::new();
+    visit(root, &agents, &mut path, &mut done)?;
+
+    let order = done
+        .iter()
+        .filter_map(|name| agents.remove(name))
+        .collect();
+
+    Ok(Resolution { order })
+}
+
+/// Depth-first walk that appends each agent after its dependencies
+fn visit(
+    name: &str,
+    agents: &BTreeMap<String, AgentConfig>,
+    path: &mut Vec<String>,
+    done: &mut Vec<String>,
+) -> Result<()> {
+    if done.iter().any(|n| n == name) {
+        return Ok(());
+    }
+
+    if let Some(start) = path.iter().position(|n| n == name) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(name.to_string());
+        return Err(AxError::Resolution(format!(
+            "Dependency cycle detected: {}",
+            cycle.join(" -> ")
+        ))
+        .into());
+    }
+
+    let agent = agents
+        .get(name)
+        .with_context(|| format!("Dependency '{}' was not fetched", name))?;
+
+    path.push(name.to_string());
+    for requirement in &agent.requires {
+        visit(&requirement.name, agents, path, done)?;
+    }
+    path.pop();
+
+    done.push(name.to_string());
+    Ok(())
+}
+
+/// Check every requirement against the single version available for it
+fn check_versions(agents: &BTreeMap<String, AgentConfig>) -> Result<()> {
+    let mut conflicts = Vec::new();
+
+    for (name, dependency) in agents {
+        let requirements: Vec<_> = agents
+            .values()
+            .flat_map(|agent| {
+                agent
+                    .requires
+                    .iter()
+                    .filter(|r| &r.name == name)
+                    .map(move |r| (agent.name.as_str(), r))
+            })
+            .collect();
+
+        if requirements.iter().all(|(_, r)| r.matches(&dependency.version)) {
+            continue;
+        }
+
+        let wanted: Vec<String> = requirements
+            .iter()
+            .map(|(by, r)| format!("{} requires {}", by, r.version))
+            .collect();
+        conflicts.push(format!(
+            "'{}' resolves to {} but {}",
+            name,
+            dependency.version,
+            wanted.join(", ")
+        ));
+    }
+
+    if !conflicts.is_empty() {
+        return Err(AxError::Resolution(format!("Version conflict: {}", conflicts.join("; "))).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(name: &str, version: &str, requires: &[&str]) -> (String, AgentConfig) {
+        let yaml = format!(
+            "name: {}\nversion: {}\ndescription: d\nauthor: a\nidentity:\n  system_prompt: p\nrequires: [{}]\n",
+            name,
+            version,
+            requires
+                .iter()
+                .map(|r| format!("\"{}\"", r))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        (name.to_string(), serde_yaml::from_str(&yaml).unwrap())
+    }
+
+    #[test]
+    fn test_install_order() {
+        let agents = BTreeMap::from([
+            agent("app", "1.0.0", &["web@^2", "shared"]),
+            agent("web", "2.1.0", &["shared@>=1.0"]),
+            agent("shared", "1.3.0", &[]),
+        ]);
+
+        let resolution = resolve_graph("app", agents).unwrap();
+        let order: Vec<&str> = resolution.order.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(order, vec!["shared", "web", "app"]);
+        assert_eq!(resolution.root().name, "app");
+        assert_eq!(resolution.dependencies().len(), 2);
+    }
+
+    #[test]
+    fn test_cycle_detected() {
+        let agents = BTreeMap::from([
+            agent("a", "1.0.0", &["b"]),
+            agent("b", "1.0.0", &["c"]),
+            agent("c", "1.0.0", &["a"]),
+        ]);
+
+        let err = resolve_graph("a", agents).unwrap_err();
+        assert!(err.to_string().contains("a -> b -> c -> a"));
+    }
+
+    #[test]
+    fn test_version_conflict() {
+        let agents = BTreeMap::from([
+            agent("app", "1.0.0", &["web", "shared@^1"]),
+            agent("web", "1.0.0", &["shared@^2"]),
+            agent("shared", "1.5.0", &[]),
+        ]);
+
+        let err = resolve_graph("app", agents).unwrap_err();
+        assert!(err.to_string().contains("web requires ^2"));
+    }
+}
@@ -0,0 +1,205 @@
+//! Schema Versions and Migrations
+//!
+//! agent.yaml files and SKILL.md frontmatter carry a `schema` version.
+//! Documents without one are version 1. Older documents are upgraded in
+//! memory before they are deserialized; documents newer than this build of
+//! ax are refused instead of being half understood.
+//!
+//! Version history:
+//! - 1: original format
+//! - 2: `allowed-tools` is space-delimited, as SKILL.md defines it. Version 1
+//!   documents often used commas (`Read,Write,Bash`), which editors reading
+//!   the SKILL.md format take as a single tool name.
+
+use anyhow::{Context, Result};
+use serde_yaml::{Mapping, Value};
+
+use super::agent::{AgentConfig, Skill};
+
+/// Schema version written by this build of ax
+pub const CURRENT_SCHEMA: u32 = 2;
+
+/// Version assumed for documents without a `schema` field
+pub const LEGACY_SCHEMA: u32 = 1;
+
+/// A migration from `version` to `version + 1`
+type Migration = fn(&mut Mapping) -> Result<()>;
+
+/// Agent migrations, indexed by the version they upgrade from
+const AGENT_MIGRATIONS: &[(u32, Migration)] = &[(1, agent_v1_to_v2)];
+
+/// SKILL.md frontmatter migrations, indexed by the version they upgrade from
+const SKILL_MIGRATIONS: &[(u32, Migration)] = &[(1, skill_v1_to_v2)];
+
+/// Parse an agent.yaml document, migrating it to the current schema
+pub fn parse_agent(yaml: &str) -> Result<AgentConfig> {
+    let mut doc: Value = serde_yaml::from_str(yaml)?;
+    migrate_agent(&mut doc)?;
+    Ok(serde_yaml::from_value(doc)?)
+}
+
+/// Parse SKILL.md frontmatter, migrating it to the current schema
+pub fn parse_skill_frontmatter(yaml: &str) -> Result<Skill> {
+    let mut doc: Value = serde_yaml::from_str(yaml)?;
+    migrate_skill(&mut doc)?;
+    Ok(serde_yaml::from_value(doc)?)
+}
+
+/// Split a Markdown document into its YAML frontmatter and body
+///
+/// Returns `None` when the document has no closed `---` frontmatter block.
+pub fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content.strip_prefix("---")?;
+    let end = rest.find("\n---")?;
+
+    let frontmatter = rest[..end].trim();
+    let body = rest[end + 4..].trim_start_matches(['\n', '\r']);
+    Some((frontmatter, body))
+}
+
+/// Upgrade an agent document in place, returning the version it started at
+pub fn migrate_agent(doc: &mut Value) -> Result<u32> {
+    migrate(doc, "agent", AGENT_MIGRATIONS)
+}
+
+/// Upgrade SKILL.md frontmatter in place, returning the version it started at
+pub fn migrate_skill(doc: &mut Value) -> Result<u32> {
+    migrate(doc, "skill", SKILL_MIGRATIONS)
+}
+
+fn migrate(doc: &mut Value, kind: &str, migrations: &[(u32, Migration)]) -> Result<u32> {
+    let mapping = doc
+        .as_mapping_mut()
+        .with_context(|| format!("Expected the {} definition to be a YAML mapping", kind))?;
+
+    let from = schema_version(mapping)?;
+    if from > CURRENT_SCHEMA {
+        anyhow::bail!(
+            "This {} uses schema version {}, but this version of ax only supports up to {}. \
+             Please upgrade ax.",
+            kind,
+            from,
+            CURRENT_SCHEMA
+        );
+    }
+
+    for (version, migration) in migrations {
+        if *version >= from {
+            migration(mapping)
+                .with_context(|| format!("Failed to migrate {} from schema {}", kind, version))?;
+        }
+    }
+
+    // Keep `schema` as the first key so it's easy to spot
+    let mut upgraded = Mapping::new();
+    upgraded.insert("schema".into(), CURRENT_SCHEMA.into());
+    for (key, value) in std::mem::take(mapping) {
+        if key.as_str() != Some("schema") {
+            upgraded.insert(key, value);
+        }
+    }
+    *mapping = upgraded;
+
+    Ok(from)
+}
+
+/// Read the `schema` field, defaulting to the legacy version
+fn schema_version(mapping: &Mapping) -> Result<u32> {
+    match mapping.get("schema") {
+        None => Ok(LEGACY_SCHEMA),
+        Some(value) => value
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version >= LEGACY_SCHEMA)
+            .with_context(|| format!("Invalid schema version: {:?}", value)),
+    }
+}
+
+fn agent_v1_to_v2(agent: &mut Mapping) -> Result<()> {
+    if let Some(Value::Sequence(skills)) = agent.get_mut("skills") {
+        for skill in skills.iter_mut().filter_map(Value::as_mapping_mut) {
+            skill_v1_to_v2(skill)?;
+        }
+    }
+    Ok(())
+}
+
+fn skill_v1_to_v2(skill: &mut Mapping) -> Result<()> {
+    if let Some(Value::String(tools)) = skill.get_mut("allowed-tools") {
+        *tools = split_tools(tools, |c| c == ',').join(" ");
+    }
+    Ok(())
+}
+
+/// Split an `allowed-tools` list at separators outside parentheses, so
+/// `Bash(git add, git commit)` stays one entry
+pub fn split_tools(tools: &str, is_separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut entries = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in tools.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 && is_separator(c) => {
+                entries.push(tools[start..i].trim());
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    entries.push(tools[start..].trim());
+    entries.retain(|entry| !entry.is_empty());
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_legacy_agent() {
+        let yaml = r#"
+name: "legacy"
+version: "1.0.0"
+description: "d"
+author: "a"
+identity:
+  system_prompt: "p"
+skills:
+  - name: "cleaner"
+    allowed-tools: "Read, Write,Bash"
+"#;
+
+        let mut doc: Value = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(migrate_agent(&mut doc).unwrap(), LEGACY_SCHEMA);
+        assert_eq!(doc["schema"].as_u64(), Some(CURRENT_SCHEMA as u64));
+
+        let agent = parse_agent(yaml).unwrap();
+        assert_eq!(agent.schema, CURRENT_SCHEMA);
+        assert_eq!(agent.skills[0].allowed_tools.as_deref(), Some("Read Write Bash"));
+    }
+
+    #[test]
+    fn test_migrate_tool_patterns() {
+        let mut skill: Value =
+            serde_yaml::from_str("allowed-tools: \"Bash(git add, git commit), Bash(npm run test),Read\"").unwrap();
+        migrate_skill(&mut skill).unwrap();
+        assert_eq!(
+            skill["allowed-tools"].as_str(),
+            Some("Bash(git add, git commit) Bash(npm run test) Read")
+        );
+
+        let tools = skill["allowed-tools"].as_str().unwrap();
+        assert_eq!(
+            split_tools(tools, char::is_whitespace),
+            ["Bash(git add, git commit)", "Bash(npm run test)", "Read"]
+        );
+    }
+
+    #[test]
+    fn test_reject_future_schema() {
+        let err = parse_skill_frontmatter("schema: 99\nname: future\n").unwrap_err();
+        assert!(err.to_string().contains("schema version 99"));
+    }
+}
@@ -0,0 +1,146 @@
+//! Registry Search
+//!
+//! Ranks registry entries against a free-text query. Every query term must
+//! match the name, tags, keywords, description or author; matches on the
+//! name count most, then tags and keywords, then the description and author.
+
+use super::agent::AgentInfo;
+
+/// Filters applied before ranking
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilters {
+    /// Only entries whose author contains this (case-insensitive)
+    pub author: Option<String>,
+    /// Only entries installable to this target id
+    pub target: Option<String>,
+}
+
+impl SearchFilters {
+    fn matches(&self, agent: &AgentInfo) -> bool {
+        if let Some(author) = &self.author {
+            if !agent.author.to_lowercase().contains(&author.to_lowercase()) {
+                return false;
+            }
+        }
+        if let Some(target) = &self.target {
+            if !agent.supports_target(target) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A registry entry with its relevance score
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    pub agent: &'a AgentInfo,
+    pub score: u32,
+}
+
+/// Entries matching every term of `query`, best match first
+///
+/// An empty query matches everything the filters allow, sorted by name.
+pub fn search<'a>(agents: &'a [AgentInfo], query: &str, filters: &SearchFilters) -> Vec<SearchHit<'a>> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+
+    let mut hits: Vec<SearchHit> = agents
+        .iter()
+        .filter(|agent| filters.matches(agent))
+        .filter_map(|agent| {
+            terms
+                .iter()
+                .map(|term| score_term(agent, term))
+                .try_fold(0, |total, score| score.map(|score| total + score))
+                .map(|score| SearchHit { agent, score })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.agent.name.cmp(&b.agent.name)));
+    hits
+}
+
+/// Score one lowercase term against an entry, `None` if it matches nowhere
+fn score_term(agent: &AgentInfo, term: &str) -> Option<u32> {
+    let name = agent.name.to_lowercase();
+    let name_score = if name == term {
+        100
+    } else if name.starts_with(term) {
+        60
+    } else if name.contains(term) {
+        40
+    } else {
+        0
+    };
+
+    let tag_score = best(&agent.tags, term, 30, 20);
+    let keyword_score = best(&agent.keywords, term, 20, 10);
+    let description_score = if agent.description.to_lowercase().contains(term) { 10 } else { 0 };
+    let author_score = if agent.author.to_lowercase().contains(term) { 5 } else { 0 };
+
+    let score = name_score + tag_score + keyword_score + description_score + author_score;
+    (score > 0).then_some(score)
+}
+
+/// Best match of a term against a list of words
+fn best(words: &[String], term: &str, exact: u32, partial: u32) -> u32 {
+    words
+        .iter()
+        .map(|word| word.to_lowercase())
+        .map(|word| {
+            if word == term {
+                exact
+            } else if word.contains(term) {
+                partial
+            } else {
+                0
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::agent::EntryKind;
+
+    fn info(name: &str, description: &str, author: &str, tags: &[&str]) -> AgentInfo {
+        AgentInfo {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            description: description.to_string(),
+            author: author.to_string(),
+            kind: EntryKind::Agent,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            keywords: Vec::new(),
+            targets: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_search_ranking_and_filters() {
+        let mut agents = vec![
+            info("rust-architect", "Senior Rust Systems Engineer", "ahmed6ww", &["rust"]),
+            info("code-cleaner", "Cleans up Rust and Python code", "acme", &["refactoring"]),
+            info("qa-testing-squad", "Écrit des tests unitaires", "acme", &["testing"]),
+        ];
+        agents[2].targets = vec!["claude".to_string()];
+
+        let names = |hits: Vec<SearchHit>| hits.iter().map(|hit| hit.agent.name.clone()).collect::<Vec<_>>();
+        let none = SearchFilters::default();
+
+        // Name and tag matches outrank description matches
+        assert_eq!(names(search(&agents, "rust", &none)), ["rust-architect", "code-cleaner"]);
+        // Every term has to match
+        assert_eq!(names(search(&agents, "rust python", &none)), ["code-cleaner"]);
+        assert_eq!(names(search(&agents, "ÉCRIT", &none)), ["qa-testing-squad"]);
+
+        let acme = SearchFilters { author: Some("ACME".into()), target: None };
+        assert_eq!(names(search(&agents, "", &acme)), ["code-cleaner", "qa-testing-squad"]);
+
+        let cursor = SearchFilters { author: None, target: Some("cursor".into()) };
+        assert_eq!(names(search(&agents, "testing", &cursor)), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,216 @@
+//! Secrets Store
+//!
+//! API keys for MCP tools are referenced in agent.yaml as `${VAR}`
+//! placeholders. Values entered during `ax install` are kept in
+//! ~/.ax/secrets.toml, readable only by the current user, so they are asked
+//! for once and never stored in the agent definition.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Secrets saved in ~/.ax/secrets.toml, keyed by variable name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SecretStore {
+    values: BTreeMap<String, String>,
+}
+
+impl SecretStore {
+    /// Load the store from a file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let store: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(store)
+    }
+
+    /// Load ~/.ax/secrets.toml, or an empty store if it doesn't exist
+    pub fn load_or_default() -> Result<Self> {
+        let path = crate::utils::paths::ax_secrets_path()?;
+        if path.exists() {
+            Self::load(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Save the store, readable and writable by the owner only
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = toml::to_string_pretty(self)?;
+        write_private(path, &content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Look up a secret, preferring the process environment
+    pub fn get(&self, name: &str) -> Option<String> {
+        std::env::var(name)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .or_else(|| self.values.get(name).cloned())
+    }
+
+    /// Store a secret, replacing any previous value
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(name.into(), value.into());
+    }
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // `mode` only applies to new files; tighten an existing one too
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(content.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, content: &str) -> std::io::Result<()> {
+    std::fs::write(path, content)
+}
+
+/// Parse a `.env` style file of `NAME=value` lines
+///
+/// Blank lines, `#` comments and a leading `export` are ignored, and values
+/// may be wrapped in single or double quotes.
+pub fn parse_env_file(content: &str) -> Result<BTreeMap<String, String>> {
+    let mut values = BTreeMap::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (name, value) = parse_assignment(line)
+            .with_context(|| format!("line {}: expected NAME=value", index + 1))?;
+        values.insert(name, value);
+    }
+
+    Ok(values)
+}
+
+/// Parse a single `NAME=value` assignment
+pub fn parse_assignment(input: &str) -> Option<(String, String)> {
+    let (name, value) = input.split_once('=')?;
+    let name = name.trim();
+    if !is_env_name(name) {
+        return None;
+    }
+
+    let value = value.trim();
+    let value = ['"', '\'']
+        .iter()
+        .find_map(|quote| value.strip_prefix(*quote)?.strip_suffix(*quote))
+        .unwrap_or(value);
+
+    Some((name.to_string(), value.to_string()))
+}
+
+/// Names of the `${VAR}` placeholders in a value, in order of appearance
+pub fn placeholders(value: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = value;
+    while let Some((start, end)) = next_placeholder(rest) {
+        names.push(&rest[start + 2..end - 1]);
+        rest = &rest[end..];
+    }
+    names
+}
+
+/// Replace each `${VAR}` placeholder, leaving it as is when `replace` returns `None`
+pub fn substitute(value: &str, mut replace: impl FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some((start, end)) = next_placeholder(rest) {
+        out.push_str(&rest[..start]);
+        match replace(&rest[start + 2..end - 1]) {
+            Some(replacement) => out.push_str(&replacement),
+            None => out.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Byte range of the next valid `${VAR}` in `input`
+fn next_placeholder(input: &str) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    loop {
+        let start = offset + input[offset..].find("${")?;
+        let end = start + 2 + input[start + 2..].find('}')?;
+        if is_env_name(&input[start + 2..end]) {
+            return Some((start, end + 1));
+        }
+        offset = start + 2;
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitute_placeholders() {
+        let value = "Bearer ${API_TOKEN} for ${ORG}, not ${not valid}";
+        assert_eq!(placeholders(value), vec!["API_TOKEN", "ORG"]);
+
+        let replaced = substitute(value, |name| {
+            (name == "API_TOKEN").then(|| format!("${{env:{}}}", name))
+        });
+        assert_eq!(replaced, "Bearer ${env:API_TOKEN} for ${ORG}, not ${not valid}");
+    }
+
+    #[test]
+    fn test_parse_env_file() {
+        let content = "# CI secrets\nexport API_KEY=\"abc=123\"\n\nORG = 'acme'\n";
+        let values = parse_env_file(content).unwrap();
+        assert_eq!(values["API_KEY"], "abc=123");
+        assert_eq!(values["ORG"], "acme");
+
+        let err = parse_env_file("API_KEY=1\nnot an assignment\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_store_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.toml");
+        std::fs::write(&path, "").unwrap();
+
+        let mut store = SecretStore::default();
+        store.set("AX_TEST_SECRET_ONLY_IN_STORE", "s3cret");
+        store.save(&path).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let loaded = SecretStore::load(&path).unwrap();
+        assert_eq!(loaded.get("AX_TEST_SECRET_ONLY_IN_STORE").as_deref(), Some("s3cret"));
+    }
+}
@@ -0,0 +1,160 @@
+//! Template Variables
+//!
+//! Expands `{name}` placeholders in system prompts and skill content.
+//!
+//! Built-in variables are `{baseDir}`, `{agentName}`, `{target}` and
+//! `{projectRoot}`; user variables come from `[variables]` in
+//! ~/.ax/config.toml and the project's ax.toml. `{{name}}` produces a
+//! literal `{name}`. Inside code spans and fenced code blocks, unknown
+//! placeholders are left as they are, since braces are common in code.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+use super::error::AxError;
+
+/// Variables available to a template
+#[derive(Debug, Clone, Default)]
+pub struct Variables {
+    values: BTreeMap<String, String>,
+}
+
+impl Variables {
+    /// Create an empty set of variables
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a variable, replacing any previous value
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.values.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set every variable from a map
+    pub fn extend(&mut self, values: &BTreeMap<String, String>) -> &mut Self {
+        for (name, value) in values {
+            self.set(name.clone(), value.clone());
+        }
+        self
+    }
+
+    /// Look up a variable
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Expand placeholders, failing on any unknown placeholder outside code
+    pub fn render(&self, input: &str) -> Result<String> {
+        let mut out = String::with_capacity(input.len());
+        let mut unknown = Vec::new();
+        let mut in_fence = false;
+
+        for (index, line) in input.split_inclusive('\n').enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+                out.push_str(line);
+                continue;
+            }
+
+            let mut in_code_span = false;
+            let mut rest = line;
+            while let Some(c) = rest.chars().next() {
+                let in_code = in_fence || in_code_span;
+
+                if c == '`' {
+                    in_code_span = !in_code_span;
+                } else if let Some((name, len)) = escaped_placeholder(rest) {
+                    if in_code {
+                        out.push_str(&rest[..len]);
+                    } else {
+                        out.push_str(&format!("{{{}}}", name));
+                    }
+                    rest = &rest[len..];
+                    continue;
+                } else if let Some((name, len)) = placeholder(rest) {
+                    match self.get(name) {
+                        Some(value) => out.push_str(value),
+                        None => {
+                            if !in_code {
+                                unknown.push(format!("{{{}}} (line {})", name, index + 1));
+                            }
+                            out.push_str(&rest[..len]);
+                        }
+                    }
+                    rest = &rest[len..];
+                    continue;
+                }
+
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+
+        if !unknown.is_empty() {
+            return Err(AxError::Validation(format!(
+                "Unknown placeholder(s) {} (use {{{{name}}}} for a literal {{name}})",
+                unknown.join(", ")
+            ))
+            .into());
+        }
+
+        Ok(out)
+    }
+}
+
+/// Match `{name}` at the start of `input`, returning the name and match length
+fn placeholder(input: &str) -> Option<(&str, usize)> {
+    let inner = input.strip_prefix('{')?;
+    let end = inner.find('}')?;
+    let name = &inner[..end];
+    is_variable_name(name).then_some((name, end + 2))
+}
+
+/// Match an escaped `{{name}}` at the start of `input`
+fn escaped_placeholder(input: &str) -> Option<(&str, usize)> {
+    let inner = input.strip_prefix("{{")?;
+    let end = inner.find("}}")?;
+    let name = &inner[..end];
+    is_variable_name(name).then_some((name, end + 4))
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_variables() {
+        let mut vars = Variables::new();
+        vars.set("baseDir", "/skills/lint").set("agentName", "cleaner");
+
+        let rendered = vars
+            .render("Run `python {baseDir}/run.py` as {agentName}. Literal {{baseDir}}.")
+            .unwrap();
+        assert_eq!(
+            rendered,
+            "Run `python /skills/lint/run.py` as cleaner. Literal {baseDir}."
+        );
+
+        // Braces in code are left alone
+        let code = "```rust\nprintln!(\"{name}\", {});\n```\nUse `{item}` and {}.\n";
+        assert_eq!(vars.render(code).unwrap(), code);
+    }
+
+    #[test]
+    fn test_unknown_placeholder() {
+        let vars = Variables::new();
+        let err = vars.render("Hello\nUse {missing} here").unwrap_err();
+        assert!(err.to_string().contains("{missing} (line 2)"));
+    }
+}
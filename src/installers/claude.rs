@@ -12,24 +12,28 @@ use colored::Colorize;
 use std::fs;
 use std::path::PathBuf;
 
+use super::common;
 use super::Installer;
-use crate::core::agent::AgentConfig;
-use crate::utils::paths;
+use crate::core::agent::{AgentConfig, McpTool, McpTransport, Skill};
+use crate::say;
+use crate::utils::paths::{self, BaseDirs};
 
 /// Installer for Claude Code
 pub struct ClaudeInstaller {
-    /// Whether to install globally
-    global: bool,
+    /// Directories paths are resolved against
+    base: BaseDirs,
 }
 
 impl ClaudeInstaller {
-    pub fn new(global: bool) -> Self {
-        Self { global }
+    /// Claude Code agents always live in the user's config directory, so
+    /// `global` makes no difference
+    pub fn new(_global: bool, base: BaseDirs) -> Self {
+        Self { base }
     }
 
     /// Get the base directory for Claude configuration
     fn get_base_dir(&self) -> Result<PathBuf> {
-        paths::claude_config_dir()
+        paths::claude_config_dir(&self.base)
             .context("Could not find Claude configuration directory")
     }
 
@@ -44,28 +48,28 @@ impl ClaudeInstaller {
     fn get_mcp_config_path(&self) -> Result<PathBuf> {
         #[cfg(target_os = "linux")]
         {
-            let config_dir = dirs::config_dir()
+            let config_dir = self.base.config_dir()
                 .context("Could not find config directory")?;
             Ok(config_dir.join("claude").join("config.json"))
         }
 
         #[cfg(target_os = "macos")]
         {
-            let home = dirs::home_dir()
+            let home = self.base.home_dir()
                 .context("Could not find home directory")?;
             Ok(home.join("Library/Application Support/Claude/config.json"))
         }
 
         #[cfg(target_os = "windows")]
         {
-            let config_dir = dirs::config_dir()
+            let config_dir = self.base.config_dir()
                 .context("Could not find config directory")?;
             Ok(config_dir.join("Claude").join("config.json"))
         }
 
         #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
         {
-            let home = dirs::home_dir()
+            let home = self.base.home_dir()
                 .context("Could not find home directory")?;
             Ok(home.join(".claude.json"))
         }
@@ -87,158 +91,44 @@ impl ClaudeInstaller {
             model
         };
 
+        let mut fields = vec![
+            ("name", agent.name.clone()),
+            ("description", agent.description.clone()),
+            ("model", model_short.to_string()),
+            ("icon", icon.to_string()),
+        ];
+
         // Format skills list for frontmatter
-        let skills_list = if !agent.skills.is_empty() {
+        if !agent.skills.is_empty() {
             let names: Vec<String> = agent.skills.iter().map(|s| s.name.clone()).collect();
-            format!("\nskills: {}", names.join(", "))
-        } else {
-            String::new()
-        };
-
-        format!(
-            r#"---
-name: {}
-description: {}
-model: {}
-icon: {}{}
----
-
-{}"#,
-            agent.name,
-            agent.description,
-            model_short,
-            icon,
-            skills_list,
-            agent.identity.system_prompt
-        )
-    }
-
-    /// Generate SKILL.md content per Agent Skills standard
-    /// Format:
-    /// ---
-    /// name: skill-name
-    /// description: Description that helps select the skill
-    /// allowed-tools: (optional)
-    /// ---
-    /// Skill instructions...
-    fn generate_skill_md(skill: &crate::core::agent::Skill) -> String {
-        let mut frontmatter = format!("---\nname: {}\n", skill.name);
-        
-        // Add description (required by Agent Skills spec)
-        if let Some(desc) = &skill.description {
-            frontmatter.push_str(&format!("description: {}\n", desc));
-        }
-        
-        // Add optional fields
-        if let Some(license) = &skill.license {
-            frontmatter.push_str(&format!("license: {}\n", license));
-        }
-        
-        if let Some(compat) = &skill.compatibility {
-            frontmatter.push_str(&format!("compatibility: {}\n", compat));
-        }
-        
-        if let Some(tools) = &skill.allowed_tools {
-            frontmatter.push_str(&format!("allowed-tools: {}\n", tools));
-        }
-        
-        if let Some(deps) = &skill.dependencies {
-            frontmatter.push_str(&format!("dependencies: {}\n", deps));
-        }
-        
-        // Add metadata if present
-        if let Some(metadata) = &skill.metadata {
-            frontmatter.push_str("metadata:\n");
-            for (key, value) in metadata {
-                frontmatter.push_str(&format!("  {}: {}\n", key, value));
-            }
+            fields.push(("skills", names.join(", ")));
         }
-        
-        frontmatter.push_str("---\n\n");
-        frontmatter.push_str(&skill.content);
-        
-        frontmatter
-    }
 
-    /// Copy scripts/, references/, and assets/ subdirectories from source to destination
-    fn copy_skill_subdirectories(source_dir: &std::path::Path, dest_dir: &std::path::Path) -> Result<()> {
-        let subdirs = ["scripts", "references", "assets"];
-        
-        for subdir in &subdirs {
-            let source_subdir = source_dir.join(subdir);
-            if source_subdir.exists() && source_subdir.is_dir() {
-                let dest_subdir = dest_dir.join(subdir);
-                Self::copy_dir_recursive(&source_subdir, &dest_subdir)?;
-            }
+        let mut markdown = String::from("---\n");
+        for (key, value) in common::with_extra_frontmatter(fields, &agent.identity.frontmatter) {
+            markdown.push_str(&format!("{}: {}\n", key, value));
         }
-        
-        Ok(())
-    }
+        markdown.push_str("---\n\n");
+        markdown.push_str(&agent.identity.system_prompt);
 
-    /// Recursively copy a directory
-    fn copy_dir_recursive(source: &std::path::Path, dest: &std::path::Path) -> Result<()> {
-        fs::create_dir_all(dest)?;
-        
-        for entry in fs::read_dir(source)? {
-            let entry = entry?;
-            let path = entry.path();
-            let dest_path = dest.join(entry.file_name());
-            
-            if path.is_dir() {
-                Self::copy_dir_recursive(&path, &dest_path)?;
-            } else {
-                fs::copy(&path, &dest_path)?;
-            }
-        }
-        
-        Ok(())
+        markdown
     }
 
-    /// Download scripts/, references/, and assets/ subdirectories from remote URL
-    fn download_skill_subdirectories(remote_base_url: &str, dest_dir: &std::path::Path) -> Result<()> {
-        let subdir_files = [
-            ("scripts", vec!["run_ruff.py", "scaffold_test.py", "main.py", "setup.py"]),
-            ("references", vec!["cleanup_rules.md", "clean_rules.md", "quad_strategy.md", "repo_strategy.md", "clean_arch.md", "REFERENCE.md"]),
-            ("assets", vec!["project_layout.txt", "template.json"]),
-        ];
-
-        for (subdir, files) in &subdir_files {
-            let dest_subdir = dest_dir.join(subdir);
-            let mut any_downloaded = false;
-
-            for file in files {
-                let file_url = format!("{}/{}/{}", remote_base_url, subdir, file);
-                let dest_file = dest_subdir.join(file);
-
-                if Self::download_remote_file(&file_url, &dest_file).is_ok() {
-                    any_downloaded = true;
-                }
-            }
-
-            if any_downloaded && !dest_subdir.exists() {
-                fs::create_dir_all(&dest_subdir)?;
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Download a single file from a remote URL (blocking)
-    fn download_remote_file(url: &str, dest_path: &std::path::Path) -> Result<()> {
-        let response = reqwest::blocking::get(url)?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("File not found: {}", url);
-        }
-
-        if let Some(parent) = dest_path.parent() {
-            fs::create_dir_all(parent)?;
+    /// Build the mcpServers entry for a tool (Claude Code tags every server with its transport)
+    fn server_config(tool: &McpTool) -> Value {
+        match tool.transport {
+            McpTransport::Stdio => json!({
+                "type": "stdio",
+                "command": tool.command,
+                "args": tool.args,
+                "env": tool.env
+            }),
+            McpTransport::Http | McpTransport::Sse => json!({
+                "type": tool.transport.to_string(),
+                "url": tool.remote_url(),
+                "headers": tool.headers
+            }),
         }
-
-        let content = response.bytes()?;
-        fs::write(dest_path, &content)?;
-
-        Ok(())
     }
 }
 
@@ -251,7 +141,7 @@ impl Installer for ClaudeInstaller {
         let agent_file = agents_dir.join(format!("{}.md", agent.name));
         let markdown_content = Self::generate_agent_markdown(agent);
         
-        fs::write(&agent_file, markdown_content)?;
+        common::write_file(&agent_file, markdown_content)?;
 
         Ok(())
     }
@@ -272,18 +162,12 @@ impl Installer for ClaudeInstaller {
             fs::create_dir_all(&skill_folder)?;
 
             // Generate SKILL.md with proper frontmatter
-            let skill_content = Self::generate_skill_md(skill);
+            let skill_content = common::render_skill_md(skill, None);
             let skill_file = skill_folder.join("SKILL.md");
-            fs::write(&skill_file, skill_content)?;
+            common::write_file(&skill_file, skill_content)?;
 
             // Copy subdirectories (scripts, references, assets)
-            if let Some(source_dir) = &skill.source_dir {
-                // Local source - copy directly
-                Self::copy_skill_subdirectories(source_dir, &skill_folder)?;
-            } else if let Some(remote_url) = &skill.remote_base_url {
-                // Remote source - download subdirectories
-                Self::download_skill_subdirectories(remote_url, &skill_folder)?;
-            }
+            common::install_skill_subdirectories(skill, &skill_folder)?;
         }
 
         Ok(())
@@ -294,47 +178,21 @@ impl Installer for ClaudeInstaller {
             return Ok(());
         }
 
-        let config_path = self.get_mcp_config_path()?;
+        let servers = agent
+            .mcp
+            .iter()
+            .map(|tool| (tool.name.clone(), Self::server_config(tool)))
+            .collect();
+        common::merge_json_servers(&self.get_mcp_config_path()?, "mcpServers", servers)?;
 
-        // Load existing config or create new one
-        let mut config: Value = if config_path.exists() {
-            let content = fs::read_to_string(&config_path)?;
-            serde_json::from_str(&content).unwrap_or_else(|_| json!({}))
-        } else {
-            json!({})
-        };
-
-        // Ensure mcpServers object exists
-        if config.get("mcpServers").is_none() {
-            config["mcpServers"] = json!({});
-        }
-
-        // Add each MCP tool
+        // Check for setup URL (API key requirement)
         for tool in &agent.mcp {
-            // Claude Code uses "type": "stdio" format
-            let tool_config = json!({
-                "type": "stdio",
-                "command": tool.command,
-                "args": tool.args,
-                "env": tool.env
-            });
-            config["mcpServers"][&tool.name] = tool_config;
-
-            // Check for setup URL (API key requirement)
             if let Some(url) = &tool.setup_url {
-                println!("\n  {} Setup required for MCP tool '{}'", "ℹ".blue().bold(), tool.name.bold());
-                println!("  {} Get your API key here: {}", "→".cyan(), url.underline().blue());
+                say!("\n  {} Setup required for MCP tool '{}'", "ℹ".blue().bold(), tool.name.bold());
+                say!("  {} Get your API key here: {}", "→".cyan(), url.underline().blue());
             }
         }
 
-        // Ensure parent directory exists
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        // Write the updated config
-        fs::write(&config_path, serde_json::to_string_pretty(&config)?)?;
-
         Ok(())
     }
 
@@ -351,9 +209,16 @@ impl Installer for ClaudeInstaller {
             fs::remove_dir_all(&skills_dir)?;
         }
 
-        // Note: MCP tools are not removed as they might be used by other agents
-
         Ok(())
     }
+
+    fn skill_dir(&self, _agent_name: &str, skill: &Skill) -> Result<Option<PathBuf>> {
+        Ok(Some(self.get_base_dir()?.join("skills").join(&skill.name)))
+    }
+
+    fn env_reference(&self, name: &str) -> Option<String> {
+        // Claude Code expands `${VAR}` in MCP server config
+        Some(format!("${{{}}}", name))
+    }
 }
 
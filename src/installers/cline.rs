@@ -0,0 +1,167 @@
+//! Cline Installer
+//!
+//! Installs agent configurations into Cline's native format.
+//!
+//! Output structure:
+//! - .clinerules/{name}-identity.md - Agent identity as a rule
+//! - .clinerules/{name}-{skill}.md - Agent skills as rules
+//! - cline_mcp_settings.json (VS Code global storage) - MCP tool configuration
+//!
+//! Global installs write rules to ~/Documents/Cline/Rules instead.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use super::common;
+use super::Installer;
+use crate::core::agent::{AgentConfig, McpTool, McpTransport};
+use crate::utils::paths::{self, BaseDirs};
+
+/// Installer for Cline
+pub struct ClineInstaller {
+    /// Whether to install globally
+    global: bool,
+
+    /// Directories paths are resolved against
+    base: BaseDirs,
+}
+
+impl ClineInstaller {
+    pub fn new(global: bool, base: BaseDirs) -> Self {
+        Self { global, base }
+    }
+
+    /// Get the rules directory
+    fn get_rules_dir(&self) -> Result<PathBuf> {
+        if self.global {
+            paths::cline_global_rules_dir(&self.base)
+                .context("Could not find Cline global rules directory")
+        } else {
+            Ok(self.base.project_path(".clinerules"))
+        }
+    }
+
+    /// Get the MCP settings path (Cline only reads a global MCP config)
+    fn get_mcp_config_path(&self) -> Result<PathBuf> {
+        paths::cline_settings_dir(&self.base)
+            .map(|dir| dir.join("cline_mcp_settings.json"))
+            .context("Could not find Cline settings directory")
+    }
+
+    /// Cline rules are plain Markdown without frontmatter
+    fn rule_frontmatter(_description: &str) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Build the cline_mcp_settings.json entry for a tool
+    fn server_config(tool: &McpTool) -> Value {
+        match tool.transport {
+            McpTransport::Stdio => json!({
+                "command": tool.command,
+                "args": tool.args,
+                "env": tool.env,
+                "disabled": false
+            }),
+            McpTransport::Http => json!({
+                "type": "streamableHttp",
+                "url": tool.remote_url(),
+                "headers": tool.headers,
+                "disabled": false
+            }),
+            McpTransport::Sse => json!({
+                "type": "sse",
+                "url": tool.remote_url(),
+                "headers": tool.headers,
+                "disabled": false
+            }),
+        }
+    }
+}
+
+impl Installer for ClineInstaller {
+    fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
+        common::write_identity_rule(&self.get_rules_dir()?, agent, "md", Self::rule_frontmatter)
+    }
+
+    fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.skills.is_empty() {
+            return Ok(());
+        }
+
+        common::write_skill_rules(&self.get_rules_dir()?, agent, "md", |_, description| {
+            Self::rule_frontmatter(description)
+        })
+    }
+
+    fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.mcp.is_empty() {
+            return Ok(());
+        }
+
+        let servers = agent
+            .mcp
+            .iter()
+            .map(|tool| (tool.name.clone(), Self::server_config(tool)))
+            .collect();
+
+        common::merge_json_servers(&self.get_mcp_config_path()?, "mcpServers", servers)
+    }
+
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        common::remove_rule_files(&self.get_rules_dir()?, agent_name, ".md")?;
+
+        Ok(())
+    }
+
+    fn mcp_config_path(&self) -> Result<Option<PathBuf>> {
+        self.get_mcp_config_path().map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_rules_and_mcp_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().join("home");
+        let installer = ClineInstaller::new(false, BaseDirs::new(&home, dir.path()));
+        let agent: AgentConfig = serde_yaml::from_str(
+            r#"
+name: demo
+version: 1.0.0
+description: d
+author: a
+identity:
+  system_prompt: Be helpful
+mcp:
+  - name: search
+    command: npx
+  - name: docs
+    transport: http
+    url: https://mcp.example.com/mcp
+"#,
+        )
+        .unwrap();
+
+        installer.install_identity(&agent).unwrap();
+        installer.install_tools(&agent).unwrap();
+
+        // Cline rules are plain Markdown
+        let rule = fs::read_to_string(dir.path().join(".clinerules/demo-identity.md")).unwrap();
+        assert!(rule.starts_with("# 🤖 demo Agent\n\nBe helpful\n"));
+
+        let settings_path = paths::cline_settings_dir(&BaseDirs::new(&home, dir.path()))
+            .unwrap()
+            .join("cline_mcp_settings.json");
+        let settings: Value = serde_json::from_str(&fs::read_to_string(settings_path).unwrap()).unwrap();
+        assert_eq!(
+            settings["mcpServers"]["search"],
+            json!({"command": "npx", "args": [], "env": {}, "disabled": false})
+        );
+        assert_eq!(settings["mcpServers"]["docs"]["type"], "streamableHttp");
+    }
+}
@@ -9,28 +9,36 @@
 //! See: https://developers.openai.com/codex/skills
 
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use super::common;
 use super::Installer;
-use crate::core::agent::AgentConfig;
-use crate::utils::paths;
+use crate::core::agent::{AgentConfig, McpTransport, Skill};
+use crate::core::error::AxError;
+use crate::core::secrets;
+use crate::say;
+use crate::utils::paths::{self, BaseDirs};
 
 /// Installer for Codex
 pub struct CodexInstaller {
     /// Whether to install globally
     #[allow(dead_code)]
     global: bool,
+
+    /// Directories paths are resolved against
+    base: BaseDirs,
 }
 
 impl CodexInstaller {
-    pub fn new(global: bool) -> Self {
-        Self { global }
+    pub fn new(global: bool, base: BaseDirs) -> Self {
+        Self { global, base }
     }
 
     /// Get the base directory for Codex configuration (~/.codex)
     fn get_base_dir(&self) -> Result<PathBuf> {
-        paths::codex_config_dir()
+        paths::codex_config_dir(&self.base)
             .context("Could not find Codex configuration directory")
     }
 
@@ -38,139 +46,19 @@ impl CodexInstaller {
     fn get_skills_dir(&self) -> Result<PathBuf> {
         Ok(self.get_base_dir()?.join("skills"))
     }
+}
 
-    /// Generate SKILL.md content per Agent Skills standard
-    /// Format:
-    /// ---
-    /// name: skill-name
-    /// description: Description that helps select the skill
-    /// allowed-tools: (optional)
-    /// ---
-    /// Skill instructions...
-    fn generate_skill_md(skill: &crate::core::agent::Skill, fallback_description: &str) -> String {
-        let mut frontmatter = format!("---\nname: {}\n", skill.name);
-        
-        // Use skill's own description or fallback to agent description
-        let description = skill.description.as_deref().unwrap_or(fallback_description);
-        frontmatter.push_str(&format!("description: {}\n", description));
-        
-        // Add optional fields per Agent Skills spec
-        if let Some(license) = &skill.license {
-            frontmatter.push_str(&format!("license: {}\n", license));
-        }
-        
-        if let Some(compat) = &skill.compatibility {
-            frontmatter.push_str(&format!("compatibility: {}\n", compat));
-        }
-        
-        if let Some(tools) = &skill.allowed_tools {
-            frontmatter.push_str(&format!("allowed-tools: {}\n", tools));
-        }
-        
-        if let Some(deps) = &skill.dependencies {
-            frontmatter.push_str(&format!("dependencies: {}\n", deps));
-        }
-        
-        // Add metadata if present
-        if let Some(metadata) = &skill.metadata {
-            frontmatter.push_str("metadata:\n");
-            for (key, value) in metadata {
-                frontmatter.push_str(&format!("  {}: {}\n", key, value));
-            }
-        }
-        
-        frontmatter.push_str("---\n\n");
-        frontmatter.push_str(&skill.content);
-        
-        frontmatter
-    }
-
-    /// Copy scripts/, references/, and assets/ subdirectories from source to destination
-    fn copy_skill_subdirectories(source_dir: &std::path::Path, dest_dir: &std::path::Path) -> Result<()> {
-        let subdirs = ["scripts", "references", "assets"];
-        
-        for subdir in &subdirs {
-            let source_subdir = source_dir.join(subdir);
-            if source_subdir.exists() && source_subdir.is_dir() {
-                let dest_subdir = dest_dir.join(subdir);
-                Self::copy_dir_recursive(&source_subdir, &dest_subdir)?;
-            }
-        }
-        
-        Ok(())
-    }
-
-    /// Recursively copy a directory
-    fn copy_dir_recursive(source: &std::path::Path, dest: &std::path::Path) -> Result<()> {
-        fs::create_dir_all(dest)?;
-        
-        for entry in fs::read_dir(source)? {
-            let entry = entry?;
-            let path = entry.path();
-            let dest_path = dest.join(entry.file_name());
-            
-            if path.is_dir() {
-                Self::copy_dir_recursive(&path, &dest_path)?;
-            } else {
-                fs::copy(&path, &dest_path)?;
-            }
-        }
-        
-        Ok(())
-    }
-
-    /// Download scripts/, references/, and assets/ subdirectories from remote URL
-    /// Uses a predefined list of common files since GitHub doesn't provide directory listing on raw URLs
-    fn download_skill_subdirectories(remote_base_url: &str, dest_dir: &std::path::Path) -> Result<()> {
-        // Common file patterns for each subdirectory
-        let subdir_files = [
-            ("scripts", vec!["run_ruff.py", "scaffold_test.py", "main.py", "setup.py"]),
-            ("references", vec!["cleanup_rules.md", "clean_rules.md", "quad_strategy.md", "repo_strategy.md", "clean_arch.md", "REFERENCE.md"]),
-            ("assets", vec!["project_layout.txt", "template.json"]),
-        ];
-
-        for (subdir, files) in &subdir_files {
-            let dest_subdir = dest_dir.join(subdir);
-            let mut any_downloaded = false;
-
-            for file in files {
-                let file_url = format!("{}/{}/{}", remote_base_url, subdir, file);
-                let dest_file = dest_subdir.join(file);
-
-                // Try to download the file (ignore 404s)
-                if Self::download_remote_file(&file_url, &dest_file).is_ok() {
-                    any_downloaded = true;
-                }
-            }
-
-            // Only create directory if files were downloaded
-            if any_downloaded && !dest_subdir.exists() {
-                fs::create_dir_all(&dest_subdir)?;
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Download a single file from a remote URL (blocking)
-    fn download_remote_file(url: &str, dest_path: &std::path::Path) -> Result<()> {
-        // Use a blocking HTTP client since we're in a sync context
-        let response = reqwest::blocking::get(url)?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("File not found: {}", url);
-        }
-
-        // Ensure parent directory exists
-        if let Some(parent) = dest_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        let content = response.bytes()?;
-        fs::write(dest_path, &content)?;
+/// Entries of a map in key order, so the TOML output is stable
+fn sorted(map: &HashMap<String, String>) -> Vec<(&String, &String)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort();
+    entries
+}
 
-        Ok(())
-    }
+/// The variable a value consists of entirely (`API_KEY` for `${API_KEY}`)
+fn whole_placeholder(value: &str) -> Option<&str> {
+    let name = value.strip_prefix("${")?.strip_suffix('}')?;
+    (secrets::placeholders(value) == [name]).then_some(name)
 }
 
 impl Installer for CodexInstaller {
@@ -195,18 +83,12 @@ impl Installer for CodexInstaller {
             fs::create_dir_all(&skill_folder)?;
 
             let skill_file = skill_folder.join("SKILL.md");
-            let skill_content = Self::generate_skill_md(skill, &agent.description);
+            let skill_content = common::render_skill_md(skill, Some(&agent.description));
             
-            fs::write(&skill_file, skill_content)?;
+            common::write_file(&skill_file, skill_content)?;
 
             // Copy subdirectories (scripts, references, assets)
-            if let Some(source_dir) = &skill.source_dir {
-                // Local source - copy directly
-                Self::copy_skill_subdirectories(source_dir, &skill_folder)?;
-            } else if let Some(remote_url) = &skill.remote_base_url {
-                // Remote source - download subdirectories
-                Self::download_skill_subdirectories(remote_url, &skill_folder)?;
-            }
+            common::install_skill_subdirectories(skill, &skill_folder)?;
         }
 
         Ok(())
@@ -236,6 +118,7 @@ impl Installer for CodexInstaller {
         // [mcp_servers.<name>]
         // command = "..."
         // args = ["...", "..."]
+        // env_vars = ["VAR"]
         // [mcp_servers.<name>.env]
         // VAR = "value"
         for tool in &agent.mcp {
@@ -248,20 +131,77 @@ impl Installer for CodexInstaller {
 
             // Build the TOML section
             let mut section = format!("\n{}\n", server_header);
-            section.push_str(&format!("command = \"{}\"\n", tool.command));
-            
-            if !tool.args.is_empty() {
-                let args_str: Vec<String> = tool.args.iter()
-                    .map(|a| format!("\"{}\"", a))
-                    .collect();
-                section.push_str(&format!("args = [{}]\n", args_str.join(", ")));
-            }
-            
-            if !tool.env.is_empty() {
-                section.push_str(&format!("\n[mcp_servers.{}.env]\n", tool.name));
-                for (key, value) in &tool.env {
-                    section.push_str(&format!("{} = \"{}\"\n", key, value));
+            match tool.transport {
+                McpTransport::Stdio => {
+                    section.push_str(&format!("command = \"{}\"\n", tool.command));
+
+                    if !tool.args.is_empty() {
+                        let args_str: Vec<String> = tool.args.iter()
+                            .map(|a| format!("\"{}\"", a))
+                            .collect();
+                        section.push_str(&format!("args = [{}]\n", args_str.join(", ")));
+                    }
+
+                    // `KEY: ${KEY}` is forwarded from the environment by name
+                    let (forwarded, env): (Vec<_>, Vec<_>) = sorted(&tool.env)
+                        .into_iter()
+                        .partition(|(key, value)| whole_placeholder(value) == Some(key.as_str()));
+                    if !forwarded.is_empty() {
+                        let names: Vec<String> =
+                            forwarded.iter().map(|(key, _)| format!("\"{}\"", key)).collect();
+                        section.push_str(&format!("env_vars = [{}]\n", names.join(", ")));
+                    }
+
+                    if !env.is_empty() {
+                        section.push_str(&format!("\n[mcp_servers.{}.env]\n", tool.name));
+                        for (key, value) in env {
+                            section.push_str(&format!("{} = \"{}\"\n", key, value));
+                        }
+                    }
+                }
+                // Streamable HTTP servers:
+                // url = "..."
+                // bearer_token_env_var = "VAR"
+                // [mcp_servers.<name>.http_headers]
+                // [mcp_servers.<name>.env_http_headers]
+                McpTransport::Http => {
+                    section.push_str(&format!("url = \"{}\"\n", tool.remote_url()));
+
+                    let mut headers = Vec::new();
+                    let mut env_headers = Vec::new();
+                    for (key, value) in sorted(&tool.headers) {
+                        let bearer = value
+                            .strip_prefix("Bearer ")
+                            .and_then(whole_placeholder)
+                            .filter(|_| key.eq_ignore_ascii_case("authorization"));
+                        if let Some(name) = bearer {
+                            section.push_str(&format!("bearer_token_env_var = \"{}\"\n", name));
+                        } else if let Some(name) = whole_placeholder(value) {
+                            env_headers.push((key, name));
+                        } else {
+                            headers.push((key, value.as_str()));
+                        }
+                    }
+
+                    for (table, entries) in [("http_headers", headers), ("env_http_headers", env_headers)] {
+                        if !entries.is_empty() {
+                            section.push_str(&format!("\n[mcp_servers.{}.{}]\n", tool.name, table));
+                            for (key, value) in entries {
+                                section.push_str(&format!("\"{}\" = \"{}\"\n", key, value));
+                            }
+                        }
+                    }
                 }
+                McpTransport::Sse => return Err(common::unsupported_transport("Codex", tool)),
+            }
+
+            // Anything left can't be read from the environment
+            if let Some(name) = section.lines().find_map(|line| secrets::placeholders(line).first().copied()) {
+                anyhow::bail!(AxError::Validation(format!(
+                    "Codex can't read ${{{}}} in MCP tool '{}'; use it as a whole `env` value named {} \
+                     or as a header value",
+                    name, tool.name, name
+                )));
             }
 
             existing_content.push_str(&section);
@@ -269,13 +209,13 @@ impl Installer for CodexInstaller {
             // Show setup URL if present
             if let Some(url) = &tool.setup_url {
                 use colored::Colorize;
-                println!("\n  {} Setup required for MCP tool '{}'", "ℹ".blue().bold(), tool.name.bold());
-                println!("  {} Get your API key here: {}", "→".cyan(), url.underline().blue());
+                say!("\n  {} Setup required for MCP tool '{}'", "ℹ".blue().bold(), tool.name.bold());
+                say!("  {} Get your API key here: {}", "→".cyan(), url.underline().blue());
             }
         }
 
         // Write the updated config
-        fs::write(&config_path, existing_content)?;
+        common::write_file(&config_path, existing_content)?;
 
         Ok(())
     }
@@ -293,4 +233,85 @@ impl Installer for CodexInstaller {
 
         Ok(())
     }
+
+    fn skill_dir(&self, _agent_name: &str, skill: &Skill) -> Result<Option<PathBuf>> {
+        Ok(Some(self.get_skills_dir()?.join(&skill.name)))
+    }
+
+    fn env_reference(&self, name: &str) -> Option<String> {
+        // Kept as `${VAR}` and turned into Codex's `env_vars`,
+        // `env_http_headers` and `bearer_token_env_var` keys
+        Some(format!("${{{}}}", name))
+    }
+
+    fn installs_identity(&self) -> bool {
+        false
+    }
+
+    fn supports_transport(&self, transport: McpTransport) -> bool {
+        transport != McpTransport::Sse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(mcp: &str) -> AgentConfig {
+        serde_yaml::from_str(&format!(
+            "name: demo\nversion: 1.0.0\ndescription: d\nauthor: a\nidentity:\n  system_prompt: hi\nmcp:\n{}",
+            mcp
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_secrets_read_from_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = CodexInstaller::new(false, BaseDirs::new(dir.path(), dir.path().join("project")));
+        installer
+            .install_tools(&agent(
+                r#"
+  - name: search
+    command: npx
+    args: ["search-server"]
+    env:
+      SEARCH_KEY: ${SEARCH_KEY}
+      REGION: eu
+  - name: docs
+    transport: http
+    url: https://mcp.example.com/mcp
+    headers:
+      Authorization: Bearer ${DOCS_TOKEN}
+      X-Team: core
+      X-Org: ${DOCS_ORG}
+"#,
+            ))
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(installer.get_base_dir().unwrap().join("config.toml")).unwrap(),
+            concat!(
+                "\n[mcp_servers.search]\ncommand = \"npx\"\nargs = [\"search-server\"]\nenv_vars = [\"SEARCH_KEY\"]\n",
+                "\n[mcp_servers.search.env]\nREGION = \"eu\"\n",
+                "\n[mcp_servers.docs]\nurl = \"https://mcp.example.com/mcp\"\nbearer_token_env_var = \"DOCS_TOKEN\"\n",
+                "\n[mcp_servers.docs.http_headers]\n\"X-Team\" = \"core\"\n",
+                "\n[mcp_servers.docs.env_http_headers]\n\"X-Org\" = \"DOCS_ORG\"\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_rejects_secret_in_args() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = CodexInstaller::new(false, BaseDirs::new(dir.path(), dir.path().join("project")));
+        let err = installer
+            .install_tools(&agent(
+                "  - name: search\n    command: npx\n    args: [\"--key=${SEARCH_KEY}\"]\n",
+            ))
+            .unwrap_err();
+
+        assert!(err.to_string().contains("SEARCH_KEY"));
+        assert!(!installer.get_base_dir().unwrap().join("config.toml").exists());
+    }
 }
@@ -0,0 +1,615 @@
+//! Shared Installer Helpers
+//!
+//! Rendering and file-merging logic reused by several installers.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::agent::{AgentConfig, McpTool, Skill};
+use crate::core::cache;
+use crate::core::error::AxError;
+use crate::utils::output;
+
+/// Write a file, recording it for the `--output json` report
+pub(super) fn write_file(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
+    let path = path.as_ref();
+    fs::write(path, contents).map_err(|source| AxError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    output::record_write(path);
+    Ok(())
+}
+
+/// Render a Markdown rule document with an optional YAML frontmatter block
+pub(super) fn render_rule(frontmatter: &[(&str, String)], title: &str, content: &str) -> String {
+    let mut out = String::new();
+
+    if !frontmatter.is_empty() {
+        out.push_str("---\n");
+        for (key, value) in frontmatter {
+            out.push_str(&format!("{}: {}\n", key, value));
+        }
+        out.push_str("---\n\n");
+    }
+
+    out.push_str(&format!("# {}\n\n{}\n", title, content));
+    out
+}
+
+/// Add target-specific frontmatter (from `targets:`), replacing keys it redefines
+pub(super) fn with_extra_frontmatter<'a>(
+    mut frontmatter: Vec<(&'a str, String)>,
+    extra: &'a [(String, String)],
+) -> Vec<(&'a str, String)> {
+    for (key, value) in extra {
+        match frontmatter.iter_mut().find(|(existing, _)| existing == key) {
+            Some(entry) => entry.1 = value.clone(),
+            None => frontmatter.push((key.as_str(), value.clone())),
+        }
+    }
+    frontmatter
+}
+
+/// Title of an agent's identity rule (e.g. "🦀 rust-architect Agent")
+pub(super) fn identity_title(agent: &AgentConfig) -> String {
+    let icon = agent.identity.icon.as_deref().unwrap_or("🤖");
+    format!("{} {} Agent", icon, agent.name)
+}
+
+/// Title of a skill rule (e.g. "rust-architect - tokio-patterns")
+pub(super) fn skill_title(agent: &AgentConfig, skill: &Skill) -> String {
+    format!("{} - {}", agent.name, skill.name)
+}
+
+/// Write an agent's identity as a `{agent}-identity.{ext}` rule file
+///
+/// `frontmatter` builds the rule frontmatter from its description.
+pub(super) fn write_identity_rule(
+    rules_dir: &Path,
+    agent: &AgentConfig,
+    ext: &str,
+    frontmatter: impl Fn(&str) -> Vec<(&'static str, String)>,
+) -> Result<()> {
+    fs::create_dir_all(rules_dir)?;
+
+    let identity_file = rules_dir.join(format!("{}-identity.{}", agent.name, ext));
+    let content = render_rule(
+        &with_extra_frontmatter(frontmatter(&agent.description), &agent.identity.frontmatter),
+        &identity_title(agent),
+        &agent.identity.system_prompt,
+    );
+    write_rule_file(&identity_file, &agent.name, content)?;
+
+    Ok(())
+}
+
+/// Write each of an agent's skills as a `{agent}-{skill}.{ext}` rule file
+///
+/// `frontmatter` builds a skill's rule frontmatter from the skill and its
+/// description.
+pub(super) fn write_skill_rules(
+    rules_dir: &Path,
+    agent: &AgentConfig,
+    ext: &str,
+    frontmatter: impl Fn(&Skill, &str) -> Vec<(&'static str, String)>,
+) -> Result<()> {
+    fs::create_dir_all(rules_dir)?;
+
+    for skill in &agent.skills {
+        let skill_file = rules_dir.join(format!("{}-{}.{}", agent.name, skill.name, ext));
+        let description = skill
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("Knowledge base for {} agent", agent.name));
+        let content = render_rule(
+            &with_extra_frontmatter(frontmatter(skill, &description), &skill.frontmatter),
+            &skill_title(agent, skill),
+            &skill.content,
+        );
+        write_rule_file(&skill_file, &agent.name, content)?;
+    }
+
+    Ok(())
+}
+
+/// Comment marking a rule file as written for an agent
+fn owner_marker(agent_name: &str) -> String {
+    format!("<!-- ax:agent {} -->", agent_name)
+}
+
+/// Write a rule file, marking it as owned by `agent_name`
+///
+/// The marker lets uninstall tell `code-lint.md` (agent `code`) apart from
+/// `code-cleaner-lint.md` (agent `code-cleaner`).
+pub(super) fn write_rule_file(path: &Path, agent_name: &str, content: String) -> Result<()> {
+    write_file(path, format!("{}\n{}\n", content.trim_end(), owner_marker(agent_name)))
+}
+
+/// The `{suffix}` rule files in `rules_dir` that were written for an agent
+///
+/// Files written before the owner marker existed are recognised by their
+/// exact name and title: `{agent}-identity` or `{agent}-{skill}` titled
+/// `{agent} - {skill}`.
+pub(super) fn owned_rule_files(rules_dir: &Path, agent_name: &str, suffix: &str) -> Result<Vec<PathBuf>> {
+    let mut owned = Vec::new();
+    if !rules_dir.exists() {
+        return Ok(owned);
+    }
+
+    let prefix = format!("{}-", agent_name);
+    let marker = owner_marker(agent_name);
+    for entry in fs::read_dir(rules_dir)? {
+        let path = entry?.path();
+        let stem = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(&prefix))
+            .and_then(|name| name.strip_suffix(suffix));
+        let (Some(stem), Ok(content)) = (stem, fs::read_to_string(&path)) else {
+            continue;
+        };
+
+        let is_owned = if content.contains("<!-- ax:agent ") {
+            content.contains(&marker)
+        } else {
+            stem == "identity"
+                || content
+                    .lines()
+                    .any(|line| line == format!("# {} - {}", agent_name, stem))
+        };
+        if is_owned {
+            owned.push(path);
+        }
+    }
+
+    owned.sort();
+    Ok(owned)
+}
+
+/// Remove the rule files written for an agent
+pub(super) fn remove_rule_files(rules_dir: &Path, agent_name: &str, suffix: &str) -> Result<()> {
+    for path in owned_rule_files(rules_dir, agent_name, suffix)? {
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
+fn block_start(id: &str) -> String {
+    format!("<!-- ax:begin {} -->", id)
+}
+
+fn block_end(id: &str) -> String {
+    format!("<!-- ax:end {} -->", id)
+}
+
+/// Insert or replace an AX-managed block inside a shared Markdown file
+///
+/// Blocks are delimited by HTML comments so user-written content around
+/// them is left untouched.
+pub(super) fn upsert_managed_block(existing: &str, id: &str, body: &str) -> String {
+    let block = format!("{}\n{}\n{}\n", block_start(id), body.trim_end(), block_end(id));
+    let start = block_start(id);
+    let end = block_end(id);
+
+    if let Some(start_idx) = existing.find(&start) {
+        if let Some(end_rel) = existing[start_idx..].find(&end) {
+            let mut end_idx = start_idx + end_rel + end.len();
+            if existing[end_idx..].starts_with('\n') {
+                end_idx += 1;
+            }
+            return format!("{}{}{}", &existing[..start_idx], block, &existing[end_idx..]);
+        }
+    }
+
+    if existing.trim().is_empty() {
+        block
+    } else if existing.ends_with("\n\n") {
+        format!("{}{}", existing, block)
+    } else if existing.ends_with('\n') {
+        format!("{}\n{}", existing, block)
+    } else {
+        format!("{}\n\n{}", existing, block)
+    }
+}
+
+/// Remove every AX-managed block owned by `owner`
+///
+/// Matches the block named `owner` itself and any block named `owner/<sub>`.
+pub(super) fn remove_managed_blocks(existing: &str, owner: &str) -> String {
+    let mut out = String::new();
+    let mut rest = existing;
+
+    while let Some(start_idx) = rest.find("<!-- ax:begin ") {
+        let header_end = match rest[start_idx..].find(" -->") {
+            Some(idx) => start_idx + idx,
+            None => break,
+        };
+        let id = &rest[start_idx + "<!-- ax:begin ".len()..header_end];
+        let end = block_end(id);
+
+        let owned = id == owner || id.starts_with(&format!("{}/", owner));
+        let end_idx = match rest[header_end..].find(&end) {
+            Some(idx) => header_end + idx + end.len(),
+            None => break,
+        };
+
+        if owned {
+            out.push_str(&rest[..start_idx]);
+            // Drop the blank line that separated the block from what precedes it
+            if out.ends_with("\n\n") || out == "\n" {
+                out.pop();
+            }
+            let mut skip = end_idx;
+            if rest[skip..].starts_with('\n') {
+                skip += 1;
+            }
+            rest = &rest[skip..];
+        } else {
+            out.push_str(&rest[..end_idx]);
+            rest = &rest[end_idx..];
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Write a managed block into a Markdown file, creating it if needed
+pub(super) fn write_managed_block(path: &Path, id: &str, body: &str) -> Result<()> {
+    let existing = if path.exists() {
+        fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
+
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    write_file(path, upsert_managed_block(&existing, id, body))?;
+
+    Ok(())
+}
+
+/// Strip all managed blocks owned by an agent from a Markdown file
+pub(super) fn clear_managed_blocks(path: &Path, owner: &str) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let existing = fs::read_to_string(path)?;
+    let updated = remove_managed_blocks(&existing, owner);
+    if updated != existing {
+        write_file(path, updated)?;
+    }
+
+    Ok(())
+}
+
+/// Merge MCP server entries into a JSON config file under `key`
+///
+/// All other keys in the file are preserved. An unparsable file is
+/// reported rather than overwritten.
+pub(super) fn merge_json_servers(path: &Path, key: &str, servers: Vec<(String, Value)>) -> Result<()> {
+    let mut config: Value = if path.exists() {
+        let content = fs::read_to_string(path)?;
+        if content.trim().is_empty() {
+            json!({})
+        } else {
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?
+        }
+    } else {
+        json!({})
+    };
+
+    if !config.get(key).map(Value::is_object).unwrap_or(false) {
+        config[key] = json!({});
+    }
+
+    for (name, server) in servers {
+        config[key][name] = server;
+    }
+
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    write_file(path, serde_json::to_string_pretty(&config)?)?;
+
+    Ok(())
+}
+
+/// Error for an MCP tool whose transport a target can't configure
+pub(super) fn unsupported_transport(target: &str, tool: &McpTool) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{} does not support {} MCP servers (tool '{}'); leave it out for this \
+         target with a `targets:` override in agent.yaml",
+        target,
+        tool.transport,
+        tool.name
+    )
+}
+
+/// Generate SKILL.md content per Agent Skills standard
+/// Format:
+/// ---
+/// name: skill-name
+/// description: Description that helps select the skill
+/// allowed-tools: (optional)
+/// ---
+/// Skill instructions...
+pub(super) fn render_skill_md(skill: &Skill, fallback_description: Option<&str>) -> String {
+    let mut fields = vec![("name", skill.name.clone())];
+
+    // Use skill's own description or the caller's fallback
+    if let Some(desc) = skill.description.as_deref().or(fallback_description) {
+        fields.push(("description", desc.to_string()));
+    }
+
+    // Add optional fields per Agent Skills spec
+    if let Some(license) = &skill.license {
+        fields.push(("license", license.clone()));
+    }
+
+    if let Some(compat) = &skill.compatibility {
+        fields.push(("compatibility", compat.clone()));
+    }
+
+    if let Some(tools) = &skill.allowed_tools {
+        fields.push(("allowed-tools", tools.clone()));
+    }
+
+    if let Some(deps) = &skill.dependencies {
+        fields.push(("dependencies", deps.clone()));
+    }
+
+    let mut frontmatter = String::from("---\n");
+    for (key, value) in with_extra_frontmatter(fields, &skill.frontmatter) {
+        frontmatter.push_str(&format!("{}: {}\n", key, value));
+    }
+
+    // Add metadata if present
+    if let Some(metadata) = &skill.metadata {
+        frontmatter.push_str("metadata:\n");
+        for (key, value) in metadata {
+            frontmatter.push_str(&format!("  {}: {}\n", key, value));
+        }
+    }
+
+    frontmatter.push_str("---\n\n");
+    frontmatter.push_str(&skill.content);
+
+    frontmatter
+}
+
+/// Ship a skill's scripts/, references/ and assets/ into `dest_dir`
+///
+/// Local skills are copied from `source_dir`; remote skills are downloaded
+/// from `remote_base_url`.
+pub(super) fn install_skill_subdirectories(skill: &Skill, dest_dir: &Path) -> Result<()> {
+    if let Some(source_dir) = &skill.source_dir {
+        copy_skill_subdirectories(source_dir, dest_dir)
+    } else if let Some(remote_url) = &skill.remote_base_url {
+        download_skill_subdirectories(remote_url, dest_dir)
+    } else {
+        Ok(())
+    }
+}
+
+/// Whether a skill has scripts/references/assets to ship
+pub(super) fn has_skill_subdirectories(skill: &Skill) -> bool {
+    skill.source_dir.is_some() || skill.remote_base_url.is_some()
+}
+
+/// Point a skill's relative scripts/, references/ and assets/ paths at the
+/// directory its files were installed to
+///
+/// `{baseDir}` is expanded earlier by the template step.
+pub(super) fn rewrite_skill_paths(content: &str, base_dir: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while !rest.is_empty() {
+        let at_boundary = out
+            .chars()
+            .last()
+            .map(|c| c.is_whitespace() || "(`\"'[<=".contains(c))
+            .unwrap_or(true);
+
+        let path = rest.strip_prefix("./").unwrap_or(rest);
+        let is_skill_path = ["scripts/", "references/", "assets/"]
+            .iter()
+            .any(|subdir| path.starts_with(subdir));
+
+        if at_boundary && is_skill_path {
+            out.push_str(base_dir.trim_end_matches('/'));
+            out.push('/');
+            rest = path;
+        }
+
+        let mut chars = rest.chars();
+        if let Some(c) = chars.next() {
+            out.push(c);
+        }
+        rest = chars.as_str();
+    }
+
+    out
+}
+
+/// Copy scripts/, references/, and assets/ subdirectories from source to destination
+fn copy_skill_subdirectories(source_dir: &Path, dest_dir: &Path) -> Result<()> {
+    let subdirs = ["scripts", "references", "assets"];
+
+    for subdir in &subdirs {
+        let source_subdir = source_dir.join(subdir);
+        if source_subdir.exists() && source_subdir.is_dir() {
+            let dest_subdir = dest_dir.join(subdir);
+            copy_dir_recursive(&source_subdir, &dest_subdir)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Recursively copy a directory
+fn copy_dir_recursive(source: &Path, dest: &Path) -> Result<()> {
+    fs::create_dir_all(dest)?;
+
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let path = entry.path();
+        let dest_path = dest.join(entry.file_name());
+
+        if path.is_dir() {
+            copy_dir_recursive(&path, &dest_path)?;
+        } else {
+            fs::copy(&path, &dest_path)?;
+            output::record_write(&dest_path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Files looked for in a remote skill's subdirectories
+///
+/// Raw hosting such as GitHub doesn't provide directory listings, so remote
+/// skills ship files with these common names.
+const REMOTE_SKILL_FILES: [(&str, &[&str]); 3] = [
+    ("scripts", &["run_ruff.py", "scaffold_test.py", "main.py", "setup.py"]),
+    ("references", &["cleanup_rules.md", "clean_rules.md", "quad_strategy.md", "repo_strategy.md", "clean_arch.md", "REFERENCE.md"]),
+    ("assets", &["project_layout.txt", "template.json"]),
+];
+
+/// A skill's scripts/ files as `(file name, content)`, for inspection
+///
+/// Reads `source_dir` for local skills and fetches (through the cache) the
+/// same files `install_skill_subdirectories` would download for remote
+/// ones. Missing or non-UTF-8 files are skipped.
+pub fn skill_scripts(skill: &Skill) -> Vec<(String, String)> {
+    if let Some(source_dir) = &skill.source_dir {
+        let Ok(entries) = fs::read_dir(source_dir.join("scripts")) else {
+            return Vec::new();
+        };
+        let mut scripts: Vec<(String, String)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| {
+                let content = fs::read_to_string(entry.path()).ok()?;
+                Some((entry.file_name().to_string_lossy().into_owned(), content))
+            })
+            .collect();
+        scripts.sort();
+        return scripts;
+    }
+
+    let Some(remote_url) = &skill.remote_base_url else {
+        return Vec::new();
+    };
+    REMOTE_SKILL_FILES[0]
+        .1
+        .iter()
+        .filter_map(|file| {
+            let url = format!("{}/scripts/{}", remote_url, file);
+            let body = cache::fetch_blocking(&url).ok()??;
+            Some((file.to_string(), String::from_utf8(body).ok()?))
+        })
+        .collect()
+}
+
+/// Download scripts/, references/, and assets/ subdirectories from remote URL
+fn download_skill_subdirectories(remote_base_url: &str, dest_dir: &Path) -> Result<()> {
+    for (subdir, files) in REMOTE_SKILL_FILES {
+        let dest_subdir = dest_dir.join(subdir);
+        let mut any_downloaded = false;
+
+        for file in files {
+            let file_url = format!("{}/{}/{}", remote_base_url, subdir, file);
+            let dest_file = dest_subdir.join(file);
+
+            // Try to download the file (ignore 404s)
+            if download_remote_file(&file_url, &dest_file).is_ok() {
+                any_downloaded = true;
+            }
+        }
+
+        // Only create directory if files were downloaded
+        if any_downloaded && !dest_subdir.exists() {
+            fs::create_dir_all(&dest_subdir)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Download a single file from a remote URL (blocking)
+fn download_remote_file(url: &str, dest_path: &Path) -> Result<()> {
+    // Installers run in a sync context, so use the blocking fetch
+    let Some(content) = cache::fetch_blocking(url)? else {
+        anyhow::bail!("File not found: {}", url);
+    };
+
+    // Ensure parent directory exists
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    write_file(dest_path, &content)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_managed_block_roundtrip() {
+        let original = "# My rules\n\nKeep this.\n";
+
+        let with_block = upsert_managed_block(original, "demo", "Hello");
+        assert!(with_block.starts_with(original));
+        assert!(with_block.contains("<!-- ax:begin demo -->\nHello\n<!-- ax:end demo -->"));
+
+        let replaced = upsert_managed_block(&with_block, "demo", "Updated");
+        assert!(replaced.contains("Updated"));
+        assert!(!replaced.contains("Hello"));
+
+        let with_skill = upsert_managed_block(&replaced, "demo/skill", "Skill");
+        let cleared = remove_managed_blocks(&with_skill, "demo");
+        assert_eq!(cleared, original);
+    }
+
+    #[test]
+    fn test_rewrite_skill_paths() {
+        let content = "Run `python scripts/lint.py`, read references/rules.md \
+                       and ./assets/logo.png, not src/scripts/x.py";
+        assert_eq!(
+            rewrite_skill_paths(content, ".cursor/skills/demo"),
+            "Run `python .cursor/skills/demo/scripts/lint.py`, read \
+             .cursor/skills/demo/references/rules.md and .cursor/skills/demo/assets/logo.png, \
+             not src/scripts/x.py"
+        );
+    }
+
+    #[test]
+    fn test_remove_keeps_other_owners() {
+        let content = upsert_managed_block("", "demo-two", "Other");
+        let content = upsert_managed_block(&content, "demo", "Mine");
+
+        let cleared = remove_managed_blocks(&content, "demo");
+        assert!(cleared.contains("Other"));
+        assert!(!cleared.contains("Mine"));
+        assert_eq!(remove_managed_blocks(&cleared, "demo-two"), "");
+    }
+}
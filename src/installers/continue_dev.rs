@@ -0,0 +1,185 @@
+//! Continue Installer
+//!
+//! Installs agent configurations into Continue's native format.
+//!
+//! Output structure (project installs, global installs use ~/.continue):
+//! - .continue/rules/{name}-identity.md - Agent identity as a rule
+//! - .continue/rules/{name}-{skill}.md - Agent skills as rules
+//! - .continue/mcpServers/{tool}.yaml - One MCP server block per tool
+
+use anyhow::{Context, Result};
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::PathBuf;
+
+use super::common;
+use super::Installer;
+use crate::core::agent::{AgentConfig, McpTool, McpTransport};
+use crate::utils::paths::{self, BaseDirs};
+
+/// Installer for Continue
+pub struct ContinueInstaller {
+    /// Whether to install globally
+    global: bool,
+
+    /// Directories paths are resolved against
+    base: BaseDirs,
+}
+
+impl ContinueInstaller {
+    pub fn new(global: bool, base: BaseDirs) -> Self {
+        Self { global, base }
+    }
+
+    /// Get the base directory for Continue configuration
+    fn get_base_dir(&self) -> Result<PathBuf> {
+        if self.global {
+            paths::continue_config_dir(&self.base)
+                .context("Could not find Continue configuration directory")
+        } else {
+            Ok(self.base.project_path(".continue"))
+        }
+    }
+
+    /// Get the rules directory
+    fn get_rules_dir(&self) -> Result<PathBuf> {
+        Ok(self.get_base_dir()?.join("rules"))
+    }
+
+    /// Get the MCP server blocks directory
+    fn get_mcp_dir(&self) -> Result<PathBuf> {
+        Ok(self.get_base_dir()?.join("mcpServers"))
+    }
+
+    /// Generate rule frontmatter (always applied, like the Cursor default)
+    fn rule_frontmatter(description: &str) -> Vec<(&'static str, String)> {
+        vec![
+            ("description", description.to_string()),
+            ("alwaysApply", "true".to_string()),
+        ]
+    }
+
+    /// Generate a standalone `mcpServers` YAML block for a tool
+    fn generate_mcp_block(tool: &McpTool) -> Result<String> {
+        let mut server = Mapping::new();
+        server.insert("name".into(), tool.name.clone().into());
+        match tool.transport {
+            McpTransport::Stdio => {
+                server.insert("command".into(), tool.command.clone().into());
+                server.insert("args".into(), serde_yaml::to_value(&tool.args)?);
+                if !tool.env.is_empty() {
+                    server.insert("env".into(), serde_yaml::to_value(&tool.env)?);
+                }
+            }
+            McpTransport::Http | McpTransport::Sse => {
+                let transport = if tool.transport == McpTransport::Http {
+                    "streamable-http"
+                } else {
+                    "sse"
+                };
+                server.insert("type".into(), transport.into());
+                server.insert("url".into(), tool.remote_url().into());
+                if !tool.headers.is_empty() {
+                    let mut options = Mapping::new();
+                    options.insert("headers".into(), serde_yaml::to_value(&tool.headers)?);
+                    server.insert("requestOptions".into(), Value::Mapping(options));
+                }
+            }
+        }
+
+        let mut block = Mapping::new();
+        block.insert("name".into(), format!("{} MCP server", tool.name).into());
+        block.insert("version".into(), "0.0.1".into());
+        block.insert("schema".into(), "v1".into());
+        block.insert("mcpServers".into(), Value::Sequence(vec![Value::Mapping(server)]));
+
+        Ok(serde_yaml::to_string(&block)?)
+    }
+}
+
+impl Installer for ContinueInstaller {
+    fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
+        common::write_identity_rule(&self.get_rules_dir()?, agent, "md", Self::rule_frontmatter)
+    }
+
+    fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.skills.is_empty() {
+            return Ok(());
+        }
+
+        common::write_skill_rules(&self.get_rules_dir()?, agent, "md", |_, description| {
+            Self::rule_frontmatter(description)
+        })
+    }
+
+    fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.mcp.is_empty() {
+            return Ok(());
+        }
+
+        let mcp_dir = self.get_mcp_dir()?;
+        fs::create_dir_all(&mcp_dir)?;
+
+        for tool in &agent.mcp {
+            let block_file = mcp_dir.join(format!("{}.yaml", tool.name));
+            common::write_file(&block_file, Self::generate_mcp_block(tool)?)?;
+        }
+
+        Ok(())
+    }
+
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        common::remove_rule_files(&self.get_rules_dir()?, agent_name, ".md")?;
+
+        Ok(())
+    }
+
+    fn env_reference(&self, name: &str) -> Option<String> {
+        // Continue resolves `${{ secrets.VAR }}` from its secrets, including
+        // the `.env` files in .continue/ and ~/.continue/
+        Some(format!("${{{{ secrets.{} }}}}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mcp_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = ContinueInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        let agent: AgentConfig = serde_yaml::from_str(
+            r#"
+name: demo
+version: 1.0.0
+description: d
+author: a
+identity:
+  system_prompt: Be helpful
+mcp:
+  - name: search
+    command: npx
+    args: ["search-server"]
+  - name: docs
+    transport: http
+    url: https://mcp.example.com/mcp
+    headers:
+      X-Team: core
+"#,
+        )
+        .unwrap();
+        installer.install_tools(&agent).unwrap();
+
+        let mcp_dir = dir.path().join(".continue/mcpServers");
+        assert_eq!(
+            fs::read_to_string(mcp_dir.join("search.yaml")).unwrap(),
+            "name: search MCP server\nversion: 0.0.1\nschema: v1\nmcpServers:\n- name: search\n  command: npx\n  args:\n  - search-server\n"
+        );
+        let docs: Value = serde_yaml::from_str(&fs::read_to_string(mcp_dir.join("docs.yaml")).unwrap()).unwrap();
+        let server = &docs["mcpServers"][0];
+        assert_eq!(server["type"].as_str(), Some("streamable-http"));
+        assert_eq!(server["url"].as_str(), Some("https://mcp.example.com/mcp"));
+        assert_eq!(server["requestOptions"]["headers"]["X-Team"].as_str(), Some("core"));
+    }
+}
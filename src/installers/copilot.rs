@@ -0,0 +1,222 @@
+//! GitHub Copilot Installer
+//!
+//! Installs agent configurations into VS Code / GitHub Copilot's native format.
+//!
+//! Output structure:
+//! - .github/copilot-instructions.md - Agent identity as a managed block
+//! - .github/instructions/{name}-{skill}.instructions.md - Skills as instruction files,
+//!   with `applyTo` following the skill's activation mode
+//! - .vscode/mcp.json - MCP tool configuration (`servers` schema)
+//!
+//! Global installs write to the VS Code user profile instead
+//! (`User/prompts/*.instructions.md` and `User/mcp.json`).
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use super::common;
+use super::Installer;
+use crate::core::agent::{Activation, AgentConfig, McpTool, McpTransport, Skill};
+use crate::utils::paths::{self, BaseDirs};
+
+/// Installer for GitHub Copilot in VS Code
+pub struct CopilotInstaller {
+    /// Whether to install globally
+    global: bool,
+
+    /// Directories paths are resolved against
+    base: BaseDirs,
+}
+
+impl CopilotInstaller {
+    pub fn new(global: bool, base: BaseDirs) -> Self {
+        Self { global, base }
+    }
+
+    /// Get the VS Code user profile directory (global installs only)
+    fn get_user_dir(&self) -> Result<PathBuf> {
+        paths::vscode_config_dir(&self.base)
+            .map(|dir| dir.join("User"))
+            .context("Could not find VS Code configuration directory")
+    }
+
+    /// Get the directory holding `*.instructions.md` files
+    fn get_instructions_dir(&self) -> Result<PathBuf> {
+        if self.global {
+            Ok(self.get_user_dir()?.join("prompts"))
+        } else {
+            Ok(self.base.project_path(".github").join("instructions"))
+        }
+    }
+
+    /// Get the MCP config path
+    fn get_mcp_config_path(&self) -> Result<PathBuf> {
+        if self.global {
+            Ok(self.get_user_dir()?.join("mcp.json"))
+        } else {
+            Ok(self.base.project_path(".vscode").join("mcp.json"))
+        }
+    }
+
+    /// Generate frontmatter for an instructions file that applies everywhere
+    fn instructions_frontmatter(description: &str) -> Vec<(&'static str, String)> {
+        vec![
+            ("description", description.to_string()),
+            ("applyTo", "\"**\"".to_string()),
+        ]
+    }
+
+    /// Generate frontmatter matching a skill's activation mode
+    ///
+    /// Instructions without `applyTo` are only used when attached by hand
+    /// or picked by their description, so only always-on skills get `**`.
+    fn skill_frontmatter(skill: &Skill, description: &str) -> Vec<(&'static str, String)> {
+        let mut frontmatter = vec![("description", description.to_string())];
+        match skill.activation() {
+            Activation::Always => frontmatter.push(("applyTo", "\"**\"".to_string())),
+            Activation::Auto => {
+                frontmatter.push(("applyTo", format!("\"{}\"", skill.globs.join(","))))
+            }
+            Activation::AgentRequested | Activation::Manual => {}
+        }
+        frontmatter
+    }
+
+    /// Build the mcp.json entry for a tool
+    fn server_config(tool: &McpTool) -> Value {
+        match tool.transport {
+            McpTransport::Stdio => json!({
+                "type": "stdio",
+                "command": tool.command,
+                "args": tool.args,
+                "env": tool.env
+            }),
+            McpTransport::Http | McpTransport::Sse => json!({
+                "type": tool.transport.to_string(),
+                "url": tool.remote_url(),
+                "headers": tool.headers
+            }),
+        }
+    }
+}
+
+impl Installer for CopilotInstaller {
+    fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
+        if self.global {
+            // There is no user-level copilot-instructions.md, so the identity
+            // becomes an instructions file that applies everywhere
+            return common::write_identity_rule(
+                &self.get_instructions_dir()?,
+                agent,
+                "instructions.md",
+                Self::instructions_frontmatter,
+            );
+        }
+
+        let body = format!(
+            "# {}\n\n{}",
+            common::identity_title(agent),
+            agent.identity.system_prompt
+        );
+        common::write_managed_block(
+            &self.base.project_path(".github").join("copilot-instructions.md"),
+            &agent.name,
+            &body,
+        )
+    }
+
+    fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.skills.is_empty() {
+            return Ok(());
+        }
+
+        common::write_skill_rules(
+            &self.get_instructions_dir()?,
+            agent,
+            "instructions.md",
+            Self::skill_frontmatter,
+        )
+    }
+
+    fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.mcp.is_empty() {
+            return Ok(());
+        }
+
+        // VS Code uses a top-level "servers" object rather than "mcpServers"
+        let servers = agent
+            .mcp
+            .iter()
+            .map(|tool| (tool.name.clone(), Self::server_config(tool)))
+            .collect();
+
+        common::merge_json_servers(&self.get_mcp_config_path()?, "servers", servers)
+    }
+
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        if !self.global {
+            common::clear_managed_blocks(
+                &self.base.project_path(".github").join("copilot-instructions.md"),
+                agent_name,
+            )?;
+        }
+
+        common::remove_rule_files(&self.get_instructions_dir()?, agent_name, ".instructions.md")?;
+
+        Ok(())
+    }
+
+    fn env_reference(&self, name: &str) -> Option<String> {
+        // VS Code expands `${env:VAR}` in mcp.json
+        Some(format!("${{env:{}}}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_skill_apply_to() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = CopilotInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        let agent: AgentConfig = serde_yaml::from_str(
+            r#"
+name: demo
+version: 1.0.0
+description: d
+author: a
+identity:
+  system_prompt: hi
+skills:
+  - name: style
+    content: Always on
+  - name: rust
+    content: Rust rules
+    globs: "**/*.rs, Cargo.toml"
+  - name: release
+    description: Release checklist
+    content: Steps
+    activation: manual
+"#,
+        )
+        .unwrap();
+        installer.install_skills(&agent).unwrap();
+
+        let read = |skill: &str| {
+            fs::read_to_string(
+                dir.path()
+                    .join(".github/instructions")
+                    .join(format!("demo-{}.instructions.md", skill)),
+            )
+            .unwrap()
+        };
+        assert!(read("style").starts_with(
+            "---\ndescription: Knowledge base for demo agent\napplyTo: \"**\"\n---\n"
+        ));
+        assert!(read("rust").contains("applyTo: \"**/*.rs,Cargo.toml\"\n"));
+        assert!(!read("release").contains("applyTo"));
+    }
+}
@@ -4,7 +4,9 @@
 //!
 //! Output structure:
 //! - .cursor/rules/{name}-identity.mdc - Agent identity as MDC rule
-//! - .cursor/rules/{skill}.mdc - Agent skills as MDC rules
+//! - .cursor/rules/{name}-{skill}.mdc - Agent skills as MDC rules, using the
+//!   skill's activation mode (always, auto-attached, agent-requested, manual)
+//! - .cursor/skills/{name}-{skill}/ - Skill scripts/, references/ and assets/
 //! - .cursor/mcp.json - MCP tool configuration
 
 use anyhow::{Context, Result};
@@ -12,29 +14,33 @@ use serde_json::{json, Value};
 use std::fs;
 use std::path::PathBuf;
 
+use super::common;
 use super::Installer;
-use crate::core::agent::AgentConfig;
-use crate::utils::paths;
+use crate::core::agent::{Activation, AgentConfig, McpTool, McpTransport, Skill};
+use crate::utils::paths::{self, BaseDirs};
 
 /// Installer for Cursor
 pub struct CursorInstaller {
     /// Whether to install globally
     global: bool,
+
+    /// Directories paths are resolved against
+    base: BaseDirs,
 }
 
 impl CursorInstaller {
-    pub fn new(global: bool) -> Self {
-        Self { global }
+    pub fn new(global: bool, base: BaseDirs) -> Self {
+        Self { global, base }
     }
 
     /// Get the base directory for Cursor configuration
     fn get_base_dir(&self) -> Result<PathBuf> {
         if self.global {
-            paths::cursor_config_dir()
+            paths::cursor_config_dir(&self.base)
                 .context("Could not find Cursor configuration directory")
         } else {
             // For non-global, use the current project's .cursor directory
-            Ok(PathBuf::from(".cursor"))
+            Ok(self.base.project_path(".cursor"))
         }
     }
 
@@ -43,47 +49,63 @@ impl CursorInstaller {
         Ok(self.get_base_dir()?.join("rules"))
     }
 
+    /// Get the directory holding a skill's scripts/references/assets,
+    /// named like the skill's rule so agents sharing a skill name don't collide
+    fn get_skill_dir(&self, agent_name: &str, skill_name: &str) -> Result<PathBuf> {
+        Ok(self.get_base_dir()?.join("skills").join(format!("{}-{}", agent_name, skill_name)))
+    }
+
     /// Get the MCP config path
     fn get_mcp_config_path(&self) -> Result<PathBuf> {
         Ok(self.get_base_dir()?.join("mcp.json"))
     }
 
     /// Generate MDC frontmatter for a rule file
-    fn generate_mdc_content(name: &str, description: &str, content: &str) -> String {
-        format!(
-            r#"---
-description: {}
-globs: 
-alwaysApply: true
----
-
-# {}
-
-{}
-"#,
-            description, name, content
-        )
+    fn mdc_frontmatter(description: &str) -> Vec<(&'static str, String)> {
+        vec![
+            ("description", description.to_string()),
+            ("globs", String::new()),
+            ("alwaysApply", "true".to_string()),
+        ]
+    }
+
+    /// Generate MDC frontmatter matching a skill's activation mode
+    fn skill_frontmatter(skill: &Skill, description: &str) -> Vec<(&'static str, String)> {
+        let description = description.to_string();
+        let (description, globs, always_apply) = match skill.activation() {
+            Activation::Always => (description, String::new(), true),
+            Activation::Auto => (String::new(), skill.globs.join(","), false),
+            Activation::AgentRequested => (description, String::new(), false),
+            Activation::Manual => (String::new(), String::new(), false),
+        };
+
+        vec![
+            ("description", description),
+            ("globs", globs),
+            ("alwaysApply", always_apply.to_string()),
+        ]
+    }
+
+    /// Build the mcp.json entry for a tool (Cursor detects remote transports from the URL)
+    fn server_config(tool: &McpTool) -> Value {
+        match tool.transport {
+            McpTransport::Stdio => json!({
+                "command": tool.command,
+                "args": tool.args,
+                "env": tool.env
+            }),
+            McpTransport::Http | McpTransport::Sse => json!({
+                "url": tool.remote_url(),
+                "headers": tool.headers
+            }),
+        }
     }
 }
 
 impl Installer for CursorInstaller {
     fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
-        let rules_dir = self.get_rules_dir()?;
-        fs::create_dir_all(&rules_dir)?;
-
         // Create the identity MDC file
-        let identity_file = rules_dir.join(format!("{}-identity.mdc", agent.name));
-
-        let icon = agent.identity.icon.as_deref().unwrap_or("🤖");
-        let mdc_content = Self::generate_mdc_content(
-            &format!("{} {} Agent", icon, agent.name),
-            &agent.description,
-            &agent.identity.system_prompt,
-        );
-
-        fs::write(&identity_file, mdc_content)?;
-
-        Ok(())
+        common::write_identity_rule(&self.get_rules_dir()?, agent, "mdc", Self::mdc_frontmatter)
     }
 
     fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
@@ -91,22 +113,18 @@ impl Installer for CursorInstaller {
             return Ok(());
         }
 
-        let rules_dir = self.get_rules_dir()?;
-        fs::create_dir_all(&rules_dir)?;
-
-        for skill in &agent.skills {
-            let skill_file = rules_dir.join(format!("{}-{}.mdc", agent.name, skill.name));
-
-            let mdc_content = Self::generate_mdc_content(
-                &format!("{} - {}", agent.name, skill.name),
-                &format!("Knowledge base for {} agent", agent.name),
-                &skill.content,
-            );
-
-            fs::write(&skill_file, mdc_content)?;
+        // Ship the skills' files and point their instructions at them
+        let mut agent = agent.clone();
+        for skill in &mut agent.skills {
+            if common::has_skill_subdirectories(skill) {
+                let skill_dir = self.get_skill_dir(&agent.name, &skill.name)?;
+                common::install_skill_subdirectories(skill, &skill_dir)?;
+                let base_dir = skill_dir.to_string_lossy().replace('\\', "/");
+                skill.content = common::rewrite_skill_paths(&skill.content, &base_dir);
+            }
         }
 
-        Ok(())
+        common::write_skill_rules(&self.get_rules_dir()?, &agent, "mdc", Self::skill_frontmatter)
     }
 
     fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
@@ -114,68 +132,109 @@ impl Installer for CursorInstaller {
             return Ok(());
         }
 
-        let config_path = self.get_mcp_config_path()?;
+        let servers = agent
+            .mcp
+            .iter()
+            .map(|tool| (tool.name.clone(), Self::server_config(tool)))
+            .collect();
 
-        // Load existing config or create new one
-        let mut config: Value = if config_path.exists() {
-            let content = fs::read_to_string(&config_path)?;
-            serde_json::from_str(&content).unwrap_or_else(|_| json!({"mcpServers": {}}))
-        } else {
-            json!({"mcpServers": {}})
-        };
-
-        // Ensure mcpServers object exists
-        if config.get("mcpServers").is_none() {
-            config["mcpServers"] = json!({});
-        }
+        common::merge_json_servers(&self.get_mcp_config_path()?, "mcpServers", servers)
+    }
 
-        // Add each MCP tool
-        for tool in &agent.mcp {
-            let tool_config = json!({
-                "command": tool.command,
-                "args": tool.args,
-                "env": tool.env
-            });
-            config["mcpServers"][&tool.name] = tool_config;
-        }
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        let rules_dir = self.get_rules_dir()?;
 
-        // Ensure parent directory exists
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
+        // Remove shipped skill files, named after the agent's skill rules
+        let prefix = format!("{}-", agent_name);
+        for rule in common::owned_rule_files(&rules_dir, agent_name, ".mdc")? {
+            let skill_name = rule
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(&prefix))
+                .and_then(|name| name.strip_suffix(".mdc"));
+
+            if let Some(skill_name) = skill_name.filter(|name| *name != "identity") {
+                let skill_dir = self.get_skill_dir(agent_name, skill_name)?;
+                if skill_dir.exists() {
+                    fs::remove_dir_all(&skill_dir)?;
+                }
+            }
         }
 
-        // Write the updated config
-        fs::write(&config_path, serde_json::to_string_pretty(&config)?)?;
+        // Remove identity and skill files for this agent
+        common::remove_rule_files(&rules_dir, agent_name, ".mdc")?;
 
         Ok(())
     }
 
-    fn uninstall(&self, agent_name: &str) -> Result<()> {
-        let rules_dir = self.get_rules_dir()?;
-
-        // Remove identity file
-        let identity_file = rules_dir.join(format!("{}-identity.mdc", agent_name));
-        if identity_file.exists() {
-            fs::remove_file(&identity_file)?;
+    fn skill_dir(&self, agent_name: &str, skill: &Skill) -> Result<Option<PathBuf>> {
+        // Skill files are only shipped when the skill has any
+        if common::has_skill_subdirectories(skill) {
+            Ok(Some(self.get_skill_dir(agent_name, &skill.name)?))
+        } else {
+            Ok(None)
         }
+    }
 
-        // Remove all skill files for this agent
-        if rules_dir.exists() {
-            for entry in fs::read_dir(&rules_dir)? {
-                let entry = entry?;
-                let file_name = entry.file_name();
-                let file_name = file_name.to_string_lossy();
-
-                if file_name.starts_with(&format!("{}-", agent_name))
-                    && file_name.ends_with(".mdc")
-                {
-                    fs::remove_file(entry.path())?;
-                }
-            }
+    fn env_reference(&self, name: &str) -> Option<String> {
+        // Cursor expands `${env:VAR}` in mcp.json
+        Some(format!("${{env:{}}}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(name: &str, source_dir: PathBuf) -> AgentConfig {
+        let mut agent: AgentConfig = serde_yaml::from_str(&format!(
+            "name: {}\nversion: 1.0.0\ndescription: d\nauthor: a\nidentity:\n  system_prompt: hi\nskills:\n  - name: lint\n    content: Run scripts/lint.sh\n",
+            name
+        ))
+        .unwrap();
+        agent.skills[0].source_dir = Some(source_dir);
+        agent
+    }
+
+    #[test]
+    fn test_agents_sharing_a_skill_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("project");
+        let installer = CursorInstaller::new(false, BaseDirs::new(dir.path().join("home"), &project));
+        for name in ["code", "review"] {
+            let scripts = dir.path().join(name).join("scripts");
+            fs::create_dir_all(&scripts).unwrap();
+            fs::write(scripts.join("lint.sh"), format!("echo {}\n", name)).unwrap();
+            installer.install_skills(&agent(name, dir.path().join(name))).unwrap();
         }
 
-        // Note: MCP tools are not removed as they might be used by other agents
+        let skills_dir = project.join(".cursor/skills");
+        let rule = fs::read_to_string(project.join(".cursor/rules/review-lint.mdc")).unwrap();
+        assert!(rule.contains(&format!("{}/scripts/lint.sh", skills_dir.join("review-lint").display())));
 
-        Ok(())
+        installer.uninstall("code").unwrap();
+        assert!(!skills_dir.join("code-lint").exists());
+        assert_eq!(
+            fs::read_to_string(skills_dir.join("review-lint/scripts/lint.sh")).unwrap(),
+            "echo review\n"
+        );
+        assert!(project.join(".cursor/rules/review-lint.mdc").exists());
+    }
+
+    #[test]
+    fn test_unparsable_mcp_config_left_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("project");
+        let installer = CursorInstaller::new(false, BaseDirs::new(dir.path().join("home"), &project));
+        let mcp_path = project.join(".cursor/mcp.json");
+        fs::create_dir_all(mcp_path.parent().unwrap()).unwrap();
+        fs::write(&mcp_path, "{ \"mcpServers\": { // mine\n").unwrap();
+
+        let agent: AgentConfig = serde_yaml::from_str(
+            "name: demo\nversion: 1.0.0\ndescription: d\nauthor: a\nidentity:\n  system_prompt: hi\nmcp:\n  - name: search\n    command: npx\n",
+        )
+        .unwrap();
+        assert!(installer.install_tools(&agent).is_err());
+        assert_eq!(fs::read_to_string(&mcp_path).unwrap(), "{ \"mcpServers\": { // mine\n");
     }
 }
@@ -0,0 +1,294 @@
+//! Gemini CLI Installer
+//!
+//! Installs agent configurations into Gemini CLI's native format.
+//!
+//! Output structure (project installs, global installs use ~/.gemini/):
+//! - GEMINI.md - Agent identity and skill imports as managed blocks
+//! - .gemini/skills/<agent>-<skill>/SKILL.md - Skills as reference files
+//! - .gemini/settings.json - MCP tool configuration (`mcpServers`)
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+
+use super::common;
+use super::Installer;
+use crate::core::agent::{AgentConfig, McpTool, McpTransport, Skill};
+use crate::utils::paths::{self, BaseDirs};
+
+/// Installer for Gemini CLI
+pub struct GeminiInstaller {
+    /// Whether to install globally
+    global: bool,
+
+    /// Directories paths are resolved against
+    base: BaseDirs,
+}
+
+impl GeminiInstaller {
+    pub fn new(global: bool, base: BaseDirs) -> Self {
+        Self { global, base }
+    }
+
+    /// Get the base directory for Gemini configuration
+    fn get_base_dir(&self) -> Result<PathBuf> {
+        if self.global {
+            paths::gemini_config_dir(&self.base)
+                .context("Could not find Gemini configuration directory")
+        } else {
+            Ok(self.base.project_path(".gemini"))
+        }
+    }
+
+    /// Get the context file path (GEMINI.md at the project root or in ~/.gemini)
+    fn get_context_path(&self) -> Result<PathBuf> {
+        if self.global {
+            Ok(self.get_base_dir()?.join("GEMINI.md"))
+        } else {
+            Ok(self.base.project_path("GEMINI.md"))
+        }
+    }
+
+    /// Get the skills directory
+    fn get_skills_dir(&self) -> Result<PathBuf> {
+        Ok(self.get_base_dir()?.join("skills"))
+    }
+
+    /// Folder name of an agent's skill, prefixed so agents sharing a skill
+    /// name don't overwrite each other
+    fn skill_folder_name(agent_name: &str, skill_name: &str) -> String {
+        format!("{}-{}", agent_name, skill_name)
+    }
+
+    /// Get the settings path holding MCP servers
+    fn get_settings_path(&self) -> Result<PathBuf> {
+        Ok(self.get_base_dir()?.join("settings.json"))
+    }
+
+    /// Path of a skill file as imported from GEMINI.md (relative to its directory)
+    fn import_path(&self, folder_name: &str) -> String {
+        if self.global {
+            format!("./skills/{}/SKILL.md", folder_name)
+        } else {
+            format!("./.gemini/skills/{}/SKILL.md", folder_name)
+        }
+    }
+
+    /// Build the settings.json entry for a tool (`httpUrl` for streamable HTTP, `url` for SSE)
+    fn server_config(tool: &McpTool) -> Value {
+        match tool.transport {
+            McpTransport::Stdio => json!({
+                "command": tool.command,
+                "args": tool.args,
+                "env": tool.env
+            }),
+            McpTransport::Http => json!({
+                "httpUrl": tool.remote_url(),
+                "headers": tool.headers
+            }),
+            McpTransport::Sse => json!({
+                "url": tool.remote_url(),
+                "headers": tool.headers
+            }),
+        }
+    }
+}
+
+impl Installer for GeminiInstaller {
+    fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
+        let body = format!(
+            "# {}\n\n{}",
+            common::identity_title(agent),
+            agent.identity.system_prompt
+        );
+
+        common::write_managed_block(&self.get_context_path()?, &agent.name, &body)
+    }
+
+    fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.skills.is_empty() {
+            return Ok(());
+        }
+
+        let skills_dir = self.get_skills_dir()?;
+        let mut imports = vec![format!("## {} skills", agent.name), String::new()];
+
+        // Each skill is packaged as a standalone SKILL.md that GEMINI.md imports
+        for skill in &agent.skills {
+            let folder_name = Self::skill_folder_name(&agent.name, &skill.name);
+            let skill_folder = skills_dir.join(&folder_name);
+            fs::create_dir_all(&skill_folder)?;
+
+            let skill_content = common::render_skill_md(skill, Some(&agent.description));
+            common::write_file(skill_folder.join("SKILL.md"), skill_content)?;
+
+            // Copy subdirectories (scripts, references, assets)
+            common::install_skill_subdirectories(skill, &skill_folder)?;
+
+            imports.push(format!("@{}", self.import_path(&folder_name)));
+        }
+
+        common::write_managed_block(
+            &self.get_context_path()?,
+            &format!("{}/skills", agent.name),
+            &imports.join("\n"),
+        )
+    }
+
+    fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.mcp.is_empty() {
+            return Ok(());
+        }
+
+        let servers = agent
+            .mcp
+            .iter()
+            .map(|tool| (tool.name.clone(), Self::server_config(tool)))
+            .collect();
+
+        // settings.json holds many unrelated keys, only mcpServers is touched
+        common::merge_json_servers(&self.get_settings_path()?, "mcpServers", servers)
+    }
+
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        let context_path = self.get_context_path()?;
+
+        // Read the skill folders back from the imports first
+        if context_path.exists() {
+            let content = fs::read_to_string(&context_path)?;
+            let marker = format!("<!-- ax:begin {}/skills -->", agent_name);
+
+            if let Some(start) = content.find(&marker) {
+                let skills_dir = self.get_skills_dir()?;
+                let block = &content[start + marker.len()..];
+                let block = &block[..block.find("<!-- ax:end").unwrap_or(block.len())];
+
+                for line in block.lines() {
+                    let folder_name = line
+                        .trim()
+                        .strip_prefix('@')
+                        .and_then(|path| path.strip_suffix("/SKILL.md"))
+                        .and_then(|path| path.rsplit('/').next());
+
+                    if let Some(folder_name) = folder_name {
+                        let skill_folder = skills_dir.join(folder_name);
+                        if skill_folder.exists() {
+                            fs::remove_dir_all(&skill_folder)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        common::clear_managed_blocks(&context_path, agent_name)?;
+
+        Ok(())
+    }
+
+    fn skill_dir(&self, agent_name: &str, skill: &Skill) -> Result<Option<PathBuf>> {
+        Ok(Some(self.get_skills_dir()?.join(Self::skill_folder_name(agent_name, &skill.name))))
+    }
+
+    fn env_reference(&self, name: &str) -> Option<String> {
+        // Gemini CLI expands `${VAR}` in settings.json
+        Some(format!("${{{}}}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent() -> AgentConfig {
+        serde_yaml::from_str(
+            r#"
+name: demo
+version: 1.0.0
+description: Demo agent
+author: a
+identity:
+  system_prompt: Be helpful
+skills:
+  - name: lint
+    content: Run the linter
+mcp:
+  - name: search
+    command: npx
+    args: ["search-server"]
+    env:
+      API_KEY: "${env:API_KEY}"
+  - name: docs
+    transport: http
+    url: https://mcp.example.com/mcp
+  - name: events
+    transport: sse
+    url: https://mcp.example.com/sse
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_settings_merge_keeps_other_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = GeminiInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        let settings_path = dir.path().join(".gemini/settings.json");
+        fs::create_dir_all(settings_path.parent().unwrap()).unwrap();
+        fs::write(
+            &settings_path,
+            r#"{"theme": "GitHub", "mcpServers": {"existing": {"command": "uvx"}}}"#,
+        )
+        .unwrap();
+
+        installer.install_tools(&agent()).unwrap();
+
+        let settings: Value = serde_json::from_str(&fs::read_to_string(&settings_path).unwrap()).unwrap();
+        assert_eq!(settings["theme"], "GitHub");
+        assert_eq!(settings["mcpServers"]["existing"]["command"], "uvx");
+        assert_eq!(
+            settings["mcpServers"]["search"],
+            json!({"command": "npx", "args": ["search-server"], "env": {"API_KEY": "${env:API_KEY}"}})
+        );
+        assert_eq!(settings["mcpServers"]["docs"]["httpUrl"], "https://mcp.example.com/mcp");
+        assert_eq!(settings["mcpServers"]["events"]["url"], "https://mcp.example.com/sse");
+    }
+
+    #[test]
+    fn test_skill_imports_and_uninstall() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = GeminiInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        let context_path = dir.path().join("GEMINI.md");
+        fs::write(&context_path, "# Project notes\n").unwrap();
+
+        installer.install_identity(&agent()).unwrap();
+        installer.install_skills(&agent()).unwrap();
+
+        let context = fs::read_to_string(&context_path).unwrap();
+        assert!(context.starts_with("# Project notes\n"));
+        assert!(context.contains("# 🤖 demo Agent\n\nBe helpful"));
+        assert!(context.contains("@./.gemini/skills/demo-lint/SKILL.md"));
+        assert!(dir.path().join(".gemini/skills/demo-lint/SKILL.md").exists());
+
+        installer.uninstall("demo").unwrap();
+        assert_eq!(fs::read_to_string(&context_path).unwrap(), "# Project notes\n");
+        assert!(!dir.path().join(".gemini/skills/demo-lint").exists());
+    }
+
+    #[test]
+    fn test_agents_sharing_a_skill_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = GeminiInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        let mut other = agent();
+        other.name = "other".to_string();
+        other.skills[0].content = "Run the other linter".to_string();
+
+        installer.install_skills(&agent()).unwrap();
+        installer.install_skills(&other).unwrap();
+        installer.uninstall("demo").unwrap();
+
+        assert!(!dir.path().join(".gemini/skills/demo-lint").exists());
+        let skill = fs::read_to_string(dir.path().join(".gemini/skills/other-lint/SKILL.md")).unwrap();
+        assert!(skill.contains("Run the other linter"));
+    }
+}
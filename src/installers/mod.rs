@@ -3,16 +3,38 @@
 //! Trait-based adapter pattern for installing agents to different editors.
 
 mod claude;
-mod cursor;
+mod cline;
 mod codex;
+mod common;
+mod continue_dev;
+mod copilot;
+mod cursor;
+mod gemini;
+mod opencode;
+mod plugin;
+mod roo;
+mod windsurf;
+mod zed;
 
 use anyhow::Result;
+use std::path::PathBuf;
 
 pub use claude::ClaudeInstaller;
-pub use cursor::CursorInstaller;
+pub use cline::ClineInstaller;
 pub use codex::CodexInstaller;
+pub use common::skill_scripts;
+pub use continue_dev::ContinueInstaller;
+pub use copilot::CopilotInstaller;
+pub use cursor::CursorInstaller;
+pub use gemini::GeminiInstaller;
+pub use opencode::OpencodeInstaller;
+pub use plugin::{discover_plugins, Plugin, PluginInstaller};
+pub use roo::RooInstaller;
+pub use windsurf::WindsurfInstaller;
+pub use zed::ZedInstaller;
 
-use crate::core::agent::AgentConfig;
+use crate::core::agent::{AgentConfig, McpTransport, Skill};
+use crate::utils::paths::BaseDirs;
 
 /// Target editor for installation
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,19 +42,79 @@ pub enum Target {
     Claude,
     Cursor,
     Codex,
+    Windsurf,
+    Copilot,
+    Gemini,
+    Cline,
+    Roo,
+    Zed,
+    Continue,
+    Opencode,
 }
 
 impl Target {
+    /// Every supported target, in detection priority order
+    pub const ALL: [Target; 11] = [
+        Target::Claude,
+        Target::Cursor,
+        Target::Codex,
+        Target::Windsurf,
+        Target::Copilot,
+        Target::Gemini,
+        Target::Cline,
+        Target::Roo,
+        Target::Zed,
+        Target::Continue,
+        Target::Opencode,
+    ];
+
+    /// Get the identifier used on the command line and in config.toml
+    pub fn id(&self) -> &'static str {
+        match self {
+            Target::Claude => "claude",
+            Target::Cursor => "cursor",
+            Target::Codex => "codex",
+            Target::Windsurf => "windsurf",
+            Target::Copilot => "copilot",
+            Target::Gemini => "gemini",
+            Target::Cline => "cline",
+            Target::Roo => "roo",
+            Target::Zed => "zed",
+            Target::Continue => "continue",
+            Target::Opencode => "opencode",
+        }
+    }
+
     /// Get the display name for the target
     pub fn display_name(&self) -> &'static str {
         match self {
             Target::Claude => "Claude Code",
             Target::Cursor => "Cursor",
             Target::Codex => "Codex",
+            Target::Windsurf => "Windsurf",
+            Target::Copilot => "GitHub Copilot (VS Code)",
+            Target::Gemini => "Gemini CLI",
+            Target::Cline => "Cline",
+            Target::Roo => "Roo Code",
+            Target::Zed => "Zed",
+            Target::Continue => "Continue",
+            Target::Opencode => "opencode",
         }
     }
 }
 
+impl std::str::FromStr for Target {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Target::ALL
+            .iter()
+            .copied()
+            .find(|target| target.id().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| anyhow::anyhow!("Unknown target '{}'", s))
+    }
+}
+
 /// Installer trait - the adapter pattern for different editors
 pub trait Installer: Send + Sync {
     /// Install the agent's identity (system prompt)
@@ -45,14 +127,68 @@ pub trait Installer: Send + Sync {
     fn install_tools(&self, agent: &AgentConfig) -> Result<()>;
 
     /// Uninstall an agent by name
+    ///
+    /// MCP servers are left in place, since other agents may use them.
     fn uninstall(&self, agent_name: &str) -> Result<()>;
+
+    /// Directory a skill's files are installed to, used for `{baseDir}`
+    ///
+    /// Installers that don't ship skill files return `None`, and the skill's
+    /// source location is used instead.
+    fn skill_dir(&self, _agent_name: &str, _skill: &Skill) -> Result<Option<PathBuf>> {
+        Ok(None)
+    }
+
+    /// How the target's MCP config refers to an environment variable
+    ///
+    /// Installers for editors that expand variables in their MCP config
+    /// return the reference (e.g. `${env:API_KEY}`) so secrets aren't written
+    /// to disk. `None` means the value itself has to be written.
+    fn env_reference(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    /// File holding the target's MCP server config, if it has a single one
+    ///
+    /// Used to point at the file when secrets have to be written into it.
+    fn mcp_config_path(&self) -> Result<Option<PathBuf>> {
+        Ok(None)
+    }
+
+    /// Whether `install_identity` writes anything
+    fn installs_identity(&self) -> bool {
+        true
+    }
+
+    /// Whether `install_skills` writes anything
+    fn installs_skills(&self) -> bool {
+        true
+    }
+
+    /// Whether `install_tools` can configure MCP servers using `transport`
+    fn supports_transport(&self, _transport: McpTransport) -> bool {
+        true
+    }
 }
 
 /// Get the appropriate installer for a target
 pub fn get_installer(target: Target, global: bool) -> Box<dyn Installer> {
+    get_installer_in(target, global, BaseDirs::system())
+}
+
+/// Get the installer for a target, resolving paths against `base`
+pub fn get_installer_in(target: Target, global: bool, base: BaseDirs) -> Box<dyn Installer> {
     match target {
-        Target::Claude => Box::new(ClaudeInstaller::new(global)),
-        Target::Cursor => Box::new(CursorInstaller::new(global)),
-        Target::Codex => Box::new(CodexInstaller::new(global)),
+        Target::Claude => Box::new(ClaudeInstaller::new(global, base)),
+        Target::Cursor => Box::new(CursorInstaller::new(global, base)),
+        Target::Codex => Box::new(CodexInstaller::new(global, base)),
+        Target::Windsurf => Box::new(WindsurfInstaller::new(global, base)),
+        Target::Copilot => Box::new(CopilotInstaller::new(global, base)),
+        Target::Gemini => Box::new(GeminiInstaller::new(global, base)),
+        Target::Cline => Box::new(ClineInstaller::new(global, base)),
+        Target::Roo => Box::new(RooInstaller::new(global, base)),
+        Target::Zed => Box::new(ZedInstaller::new(global, base)),
+        Target::Continue => Box::new(ContinueInstaller::new(global, base)),
+        Target::Opencode => Box::new(OpencodeInstaller::new(global, base)),
     }
 }
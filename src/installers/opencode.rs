@@ -0,0 +1,345 @@
+//! opencode Installer
+//!
+//! Installs agent configurations into opencode's native format.
+//!
+//! Output structure (project installs, global installs use ~/.config/opencode):
+//! - AGENTS.md - Agent identity as a managed block
+//! - .opencode/instructions/{name}/{name}-{skill}.md - Skills, referenced from `instructions`
+//! - opencode.json - `instructions` entries and MCP tool configuration (`mcp`)
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+
+use super::common;
+use super::Installer;
+use crate::core::agent::{AgentConfig, McpTool, McpTransport};
+use crate::utils::paths::{self, BaseDirs};
+
+/// Installer for opencode
+pub struct OpencodeInstaller {
+    /// Whether to install globally
+    global: bool,
+
+    /// Directories paths are resolved against
+    base: BaseDirs,
+}
+
+impl OpencodeInstaller {
+    pub fn new(global: bool, base: BaseDirs) -> Self {
+        Self { global, base }
+    }
+
+    /// Get the global opencode configuration directory
+    fn get_config_dir(&self) -> Result<PathBuf> {
+        paths::opencode_config_dir(&self.base)
+            .context("Could not find opencode configuration directory")
+    }
+
+    /// Get the AGENTS.md path
+    fn get_agents_md_path(&self) -> Result<PathBuf> {
+        if self.global {
+            Ok(self.get_config_dir()?.join("AGENTS.md"))
+        } else {
+            Ok(self.base.project_path("AGENTS.md"))
+        }
+    }
+
+    /// Get the directory holding skill instruction files
+    fn get_instructions_dir(&self) -> Result<PathBuf> {
+        if self.global {
+            Ok(self.get_config_dir()?.join("instructions"))
+        } else {
+            Ok(self.base.project_path(".opencode").join("instructions"))
+        }
+    }
+
+    /// Get the directory holding an agent's skill files
+    fn get_agent_dir(&self, agent_name: &str) -> Result<PathBuf> {
+        Ok(self.get_instructions_dir()?.join(agent_name))
+    }
+
+    /// Get the opencode.json path
+    fn get_config_path(&self) -> Result<PathBuf> {
+        if self.global {
+            Ok(self.get_config_dir()?.join("opencode.json"))
+        } else {
+            Ok(self.base.project_path("opencode.json"))
+        }
+    }
+
+    /// The `instructions` glob matching an agent's skill files
+    fn instructions_glob(&self, agent_name: &str) -> Result<String> {
+        self.instructions_entry(&format!("{}/*.md", agent_name))
+    }
+
+    /// The glob older versions used, which also matched agents sharing the
+    /// name as a prefix (`code-*.md` matched `code-cleaner-lint.md`)
+    fn legacy_instructions_glob(&self, agent_name: &str) -> Result<String> {
+        self.instructions_entry(&format!("{}-*.md", agent_name))
+    }
+
+    /// An `instructions` entry for a path in the instructions directory
+    fn instructions_entry(&self, file: &str) -> Result<String> {
+        // Project entries stay relative to opencode.json
+        let dir = if self.global {
+            self.get_instructions_dir()?
+        } else {
+            PathBuf::from(".opencode").join("instructions")
+        };
+        Ok(dir.join(file).to_string_lossy().replace('\\', "/"))
+    }
+
+    /// Remove skill files and the `instructions` entry left by older versions
+    fn remove_legacy_instructions(&self, agent_name: &str, config: &mut Value) -> Result<()> {
+        common::remove_rule_files(&self.get_instructions_dir()?, agent_name, ".md")?;
+
+        let legacy = self.legacy_instructions_glob(agent_name)?;
+        if let Some(instructions) = config.get_mut("instructions").and_then(Value::as_array_mut) {
+            instructions.retain(|entry| entry.as_str() != Some(legacy.as_str()));
+        }
+        Ok(())
+    }
+
+    /// Skill files are plain Markdown without frontmatter
+    fn rule_frontmatter(_description: &str) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Build the `mcp` entry for a tool
+    fn server_config(tool: &McpTool) -> Value {
+        match tool.transport {
+            McpTransport::Stdio => {
+                // opencode takes the command and its arguments as a single array
+                let mut command = vec![tool.command.clone()];
+                command.extend(tool.args.iter().cloned());
+                json!({
+                    "type": "local",
+                    "command": command,
+                    "environment": tool.env,
+                    "enabled": true
+                })
+            }
+            McpTransport::Http | McpTransport::Sse => json!({
+                "type": "remote",
+                "url": tool.remote_url(),
+                "headers": tool.headers,
+                "enabled": true
+            }),
+        }
+    }
+
+    /// Load opencode.json, apply `update` and write it back
+    fn update_config(&self, update: impl FnOnce(&mut Value) -> Result<()>) -> Result<()> {
+        let config_path = self.get_config_path()?;
+
+        let mut config: Value = if config_path.exists() {
+            let content = fs::read_to_string(&config_path)?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", config_path.display()))?
+        } else {
+            json!({ "$schema": "https://opencode.ai/config.json" })
+        };
+
+        update(&mut config)?;
+
+        if let Some(parent) = config_path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        common::write_file(&config_path, serde_json::to_string_pretty(&config)?)?;
+
+        Ok(())
+    }
+}
+
+impl Installer for OpencodeInstaller {
+    fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
+        let body = format!(
+            "# {}\n\n{}",
+            common::identity_title(agent),
+            agent.identity.system_prompt
+        );
+        common::write_managed_block(&self.get_agents_md_path()?, &agent.name, &body)
+    }
+
+    fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.skills.is_empty() {
+            return Ok(());
+        }
+
+        common::write_skill_rules(
+            &self.get_agent_dir(&agent.name)?,
+            agent,
+            "md",
+            |_, description| Self::rule_frontmatter(description),
+        )?;
+
+        // Reference the skill files from opencode.json so they are loaded
+        let glob = self.instructions_glob(&agent.name)?;
+        self.update_config(|config| {
+            self.remove_legacy_instructions(&agent.name, config)?;
+            if !config.get("instructions").map(Value::is_array).unwrap_or(false) {
+                config["instructions"] = json!([]);
+            }
+            if let Some(instructions) = config["instructions"].as_array_mut() {
+                if !instructions.iter().any(|entry| entry.as_str() == Some(glob.as_str())) {
+                    instructions.push(json!(glob));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.mcp.is_empty() {
+            return Ok(());
+        }
+
+        let servers = agent
+            .mcp
+            .iter()
+            .map(|tool| (tool.name.clone(), Self::server_config(tool)))
+            .collect();
+
+        common::merge_json_servers(&self.get_config_path()?, "mcp", servers)
+    }
+
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        common::clear_managed_blocks(&self.get_agents_md_path()?, agent_name)?;
+        let agent_dir = self.get_agent_dir(agent_name)?;
+        if agent_dir.exists() {
+            fs::remove_dir_all(&agent_dir)?;
+        }
+
+        if self.get_config_path()?.exists() {
+            let glob = self.instructions_glob(agent_name)?;
+            self.update_config(|config| {
+                self.remove_legacy_instructions(agent_name, config)?;
+                if let Some(instructions) = config
+                    .get_mut("instructions")
+                    .and_then(Value::as_array_mut)
+                {
+                    instructions.retain(|entry| entry.as_str() != Some(glob.as_str()));
+                }
+                Ok(())
+            })?;
+        } else {
+            common::remove_rule_files(&self.get_instructions_dir()?, agent_name, ".md")?;
+        }
+
+        Ok(())
+    }
+
+    fn env_reference(&self, name: &str) -> Option<String> {
+        // opencode substitutes `{env:VAR}` in opencode.json
+        Some(format!("{{env:{}}}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent() -> AgentConfig {
+        serde_yaml::from_str(
+            r#"
+name: demo
+version: 1.0.0
+description: d
+author: a
+identity:
+  system_prompt: Be helpful
+skills:
+  - name: lint
+    content: Run the linter
+mcp:
+  - name: search
+    command: npx
+    args: ["search-server"]
+  - name: docs
+    transport: http
+    url: https://mcp.example.com/mcp
+"#,
+        )
+        .unwrap()
+    }
+
+    fn read_config(dir: &std::path::Path) -> Value {
+        serde_json::from_str(&fs::read_to_string(dir.join("opencode.json")).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_config_merge_and_instructions() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = OpencodeInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        fs::write(
+            dir.path().join("opencode.json"),
+            r#"{"model": "anthropic/claude-sonnet-4", "instructions": ["CONTRIBUTING.md"]}"#,
+        )
+        .unwrap();
+
+        installer.install_skills(&agent()).unwrap();
+        // Reinstalling doesn't add the entry twice
+        installer.install_skills(&agent()).unwrap();
+        installer.install_tools(&agent()).unwrap();
+
+        let config = read_config(dir.path());
+        assert_eq!(config["model"], "anthropic/claude-sonnet-4");
+        assert_eq!(
+            config["instructions"],
+            json!(["CONTRIBUTING.md", ".opencode/instructions/demo/*.md"])
+        );
+        assert_eq!(
+            config["mcp"]["search"],
+            json!({"type": "local", "command": ["npx", "search-server"], "environment": {}, "enabled": true})
+        );
+        assert_eq!(config["mcp"]["docs"]["type"], "remote");
+        assert!(dir.path().join(".opencode/instructions/demo/demo-lint.md").exists());
+
+        installer.uninstall("demo").unwrap();
+        let config = read_config(dir.path());
+        assert_eq!(config["instructions"], json!(["CONTRIBUTING.md"]));
+        assert_eq!(config["model"], "anthropic/claude-sonnet-4");
+        assert!(!dir.path().join(".opencode/instructions/demo").exists());
+    }
+
+    #[test]
+    fn test_agents_sharing_a_name_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = OpencodeInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        let mut cleaner = agent();
+        cleaner.name = "demo-cleaner".to_string();
+
+        // An install by an older version, using the prefix glob
+        let legacy = dir.path().join(".opencode/instructions/demo-lint.md");
+        fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+        fs::write(&legacy, "# demo - lint\n\nRun the linter\n").unwrap();
+        fs::write(
+            dir.path().join("opencode.json"),
+            r#"{"instructions": [".opencode/instructions/demo-*.md"]}"#,
+        )
+        .unwrap();
+
+        installer.install_skills(&agent()).unwrap();
+        installer.install_skills(&cleaner).unwrap();
+        assert!(!legacy.exists());
+        assert_eq!(
+            read_config(dir.path())["instructions"],
+            json!([".opencode/instructions/demo/*.md", ".opencode/instructions/demo-cleaner/*.md"])
+        );
+
+        installer.uninstall("demo").unwrap();
+        assert_eq!(
+            read_config(dir.path())["instructions"],
+            json!([".opencode/instructions/demo-cleaner/*.md"])
+        );
+        assert!(dir
+            .path()
+            .join(".opencode/instructions/demo-cleaner/demo-cleaner-lint.md")
+            .exists());
+    }
+}
@@ -0,0 +1,412 @@
+//! Installer Plugins
+//!
+//! Runs external `ax-installer-<name>` executables as installers, so teams
+//! can support in-house tools without changing the `Target` enum.
+//!
+//! Plugins are discovered in ~/.ax/plugins and on PATH (in that order).
+//! For every operation ax runs the plugin once, from the project directory,
+//! and writes a JSON request to its stdin:
+//!
+//! ```json
+//! {
+//!   "protocol": 1,
+//!   "operation": "identity" | "skills" | "tools" | "uninstall",
+//!   "scope": "project" | "global",
+//!   "agent_name": "rust-architect",
+//!   "agent": { ...AgentConfig... },
+//!   "identity_frontmatter": { "<key>": "<value>" },
+//!   "skill_sources": {
+//!     "<skill>": { "source_dir": "...", "remote_base_url": "...", "frontmatter": { ... } }
+//!   }
+//! }
+//! ```
+//!
+//! `agent` already has the plugin's `targets:` override applied; the extra
+//! frontmatter it sets is passed alongside, since it isn't part of the
+//! agent.yaml form. `agent`, `identity_frontmatter` and `skill_sources` are
+//! omitted for `uninstall`. The plugin
+//! answers on stdout with `{"files": [...], "warnings": [...]}` (both
+//! optional, empty output is accepted) and exits non-zero on failure, with
+//! the reason on stderr.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use super::Installer;
+use crate::core::agent::AgentConfig;
+use crate::utils::paths::{self, BaseDirs};
+use crate::utils::{output, ui};
+
+/// Executable name prefix of installer plugins
+pub const PLUGIN_PREFIX: &str = "ax-installer-";
+
+/// Version of the JSON protocol spoken with plugins
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// A discovered installer plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plugin {
+    /// Target name (the part after `ax-installer-`)
+    pub name: String,
+
+    /// Path to the plugin executable
+    pub path: PathBuf,
+}
+
+impl Plugin {
+    /// Find a plugin by target name
+    pub fn find(name: &str) -> Option<Plugin> {
+        discover_plugins().into_iter().find(|plugin| plugin.name == name)
+    }
+
+    /// Get an installer that delegates to this plugin
+    pub fn installer(&self, global: bool, base: BaseDirs) -> PluginInstaller {
+        PluginInstaller {
+            plugin: self.clone(),
+            global,
+            base,
+        }
+    }
+}
+
+/// Discover all installer plugins, earlier directories taking precedence
+pub fn discover_plugins() -> Vec<Plugin> {
+    let mut dirs = Vec::new();
+    if let Ok(plugins_dir) = paths::ax_plugins_dir() {
+        dirs.push(plugins_dir);
+    }
+    if let Some(path) = std::env::var_os("PATH") {
+        dirs.extend(std::env::split_paths(&path));
+    }
+
+    let mut plugins: Vec<Plugin> = Vec::new();
+    for dir in dirs {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        let mut found: Vec<Plugin> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                let file_name = path.file_name()?.to_str()?.to_string();
+                let name = plugin_name(&file_name)?;
+                is_executable(&path).then_some(Plugin { name, path })
+            })
+            .collect();
+        found.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for plugin in found {
+            if !plugins.iter().any(|p| p.name == plugin.name) {
+                plugins.push(plugin);
+            }
+        }
+    }
+
+    plugins
+}
+
+/// Extract the target name from a plugin file name
+fn plugin_name(file_name: &str) -> Option<String> {
+    let name = file_name.strip_prefix(PLUGIN_PREFIX)?;
+    let name = if cfg!(windows) {
+        name.strip_suffix(".exe")?
+    } else {
+        name
+    };
+
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then(|| name.to_string())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Request sent to a plugin on stdin
+#[derive(Debug, Serialize)]
+struct PluginRequest<'a> {
+    protocol: u32,
+    operation: &'a str,
+    scope: &'a str,
+    agent_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    agent: Option<&'a AgentConfig>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    identity_frontmatter: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    skill_sources: BTreeMap<String, SkillSource>,
+}
+
+/// Where a skill's scripts/references/assets come from and the frontmatter
+/// its `targets:` override adds (not part of AgentConfig's serde form)
+#[derive(Debug, Serialize)]
+struct SkillSource {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_dir: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_base_url: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    frontmatter: BTreeMap<String, String>,
+}
+
+/// Response read from a plugin's stdout
+#[derive(Debug, Default, Deserialize)]
+pub struct PluginResponse {
+    /// Files written by the plugin
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+
+    /// Non-fatal issues to show the user
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Installer that delegates every operation to a plugin executable
+pub struct PluginInstaller {
+    plugin: Plugin,
+    global: bool,
+    base: BaseDirs,
+}
+
+impl PluginInstaller {
+    /// Run one operation and parse the plugin's response
+    fn run(&self, operation: &str, agent_name: &str, agent: Option<&AgentConfig>) -> Result<PluginResponse> {
+        let skill_sources = agent
+            .map(|agent| {
+                agent
+                    .skills
+                    .iter()
+                    .filter(|skill| {
+                        skill.source_dir.is_some()
+                            || skill.remote_base_url.is_some()
+                            || !skill.frontmatter.is_empty()
+                    })
+                    .map(|skill| {
+                        (
+                            skill.name.clone(),
+                            SkillSource {
+                                source_dir: skill.source_dir.clone(),
+                                remote_base_url: skill.remote_base_url.clone(),
+                                frontmatter: skill.frontmatter.iter().cloned().collect(),
+                            },
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let request = PluginRequest {
+            protocol: PROTOCOL_VERSION,
+            operation,
+            scope: if self.global { "global" } else { "project" },
+            agent_name,
+            agent,
+            identity_frontmatter: agent
+                .map(|agent| agent.identity.frontmatter.iter().cloned().collect())
+                .unwrap_or_default(),
+            skill_sources,
+        };
+        let payload = serde_json::to_vec(&request)?;
+
+        let mut command = Command::new(&self.plugin.path);
+        if let Some(project) = self.base.project_dir() {
+            command.current_dir(project);
+        }
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run plugin {}", self.plugin.path.display()))?;
+
+        // Write the request from another thread so a plugin that writes a lot
+        // before it has read all of its input can't block on a full pipe
+        let mut stdin = child.stdin.take().context("Plugin stdin is not piped")?;
+        let writer = std::thread::spawn(move || stdin.write_all(&payload));
+
+        let output = child.wait_with_output()?;
+        let written = writer
+            .join()
+            .map_err(|_| anyhow::anyhow!("Plugin '{}' request writer panicked", self.plugin.name))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(
+                "Plugin '{}' failed during {} ({}): {}",
+                self.plugin.name,
+                operation,
+                output.status,
+                stderr.trim()
+            );
+        }
+
+        written.with_context(|| {
+            format!("Failed to send the {} request to plugin '{}'", operation, self.plugin.name)
+        })?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let response: PluginResponse = if stdout.trim().is_empty() {
+            PluginResponse::default()
+        } else {
+            serde_json::from_str(&stdout).with_context(|| {
+                format!("Plugin '{}' returned an invalid response", self.plugin.name)
+            })?
+        };
+
+        for warning in &response.warnings {
+            ui::print_warning(warning);
+        }
+        for file in &response.files {
+            ui::print_bullet(&file.display().to_string());
+            // Relative paths are relative to the project directory the plugin ran in
+            output::record_write(&self.base.project_path(file));
+        }
+
+        Ok(response)
+    }
+}
+
+impl Installer for PluginInstaller {
+    fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
+        self.run("identity", &agent.name, Some(agent)).map(|_| ())
+    }
+
+    fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
+        self.run("skills", &agent.name, Some(agent)).map(|_| ())
+    }
+
+    fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
+        self.run("tools", &agent.name, Some(agent)).map(|_| ())
+    }
+
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        self.run("uninstall", agent_name, None).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(plugin_name("ax-installer-acme").as_deref(), Some("acme"));
+        assert_eq!(plugin_name("ax-installer-"), None);
+        assert_eq!(plugin_name("ax-other"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_roundtrip() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("ax-installer-echo");
+        let request_log = dir.path().join("request.json");
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\ncat > {}\necho '{{\"files\": [\"out.md\"], \"warnings\": []}}'\n",
+                request_log.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let agent: AgentConfig = serde_yaml::from_str(
+            r#"
+name: demo
+version: 1.0.0
+description: d
+author: a
+identity:
+  system_prompt: hi
+skills:
+  - name: lint
+    content: Run the linter
+  - name: docs
+    content: Write docs
+targets:
+  echo:
+    skills: [lint]
+    frontmatter:
+      mode: agent
+    skill_frontmatter:
+      globs: "*.rs"
+"#,
+        )
+        .unwrap();
+        let agent = agent.for_target("echo").unwrap();
+
+        let installer = Plugin {
+            name: "echo".to_string(),
+            path: script,
+        }
+        .installer(false, BaseDirs::system());
+        let response = installer.run("identity", &agent.name, Some(&agent)).unwrap();
+        assert_eq!(response.files, vec![PathBuf::from("out.md")]);
+
+        let request: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&request_log).unwrap()).unwrap();
+        assert_eq!(request["operation"], "identity");
+        assert_eq!(request["scope"], "project");
+        assert_eq!(request["agent"]["identity"]["system_prompt"], "hi");
+
+        // The plugin's `targets:` override reaches it
+        assert_eq!(request["agent"]["skills"].as_array().unwrap().len(), 1);
+        assert_eq!(request["identity_frontmatter"]["mode"], "agent");
+        assert_eq!(request["skill_sources"]["lint"]["frontmatter"]["globs"], "*.rs");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_writing_before_reading() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Fill stdout and stderr before reading a request larger than a pipe buffer
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("ax-installer-chatty");
+        std::fs::write(
+            &script,
+            "#!/bin/sh
+head -c 200000 /dev/zero >&2
+printf '%200000s' '' \ncat > /dev/null
+",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let agent: AgentConfig = serde_yaml::from_str(&format!(
+            "name: demo\nversion: 1.0.0\ndescription: d\nauthor: a\nidentity:\n  system_prompt: {}\n",
+            "x".repeat(200_000)
+        ))
+        .unwrap();
+
+        let installer = Plugin {
+            name: "chatty".to_string(),
+            path: script,
+        }
+        .installer(false, BaseDirs::system());
+        let response = installer.run("identity", &agent.name, Some(&agent)).unwrap();
+        assert!(response.files.is_empty());
+    }
+}
@@ -0,0 +1,297 @@
+//! Roo Code Installer
+//!
+//! Installs agent configurations into Roo Code's native format.
+//!
+//! Output structure (project installs, global installs use ~/.roo and
+//! Roo's VS Code global storage):
+//! - .roo/rules/{name}-identity.md - Agent identity as a rule
+//! - .roo/rules/{name}-{skill}.md - Agent skills as rules
+//! - .roomodes - Custom mode carrying the agent's icon and system prompt
+//! - .roo/mcp.json - MCP tool configuration
+
+use anyhow::{Context, Result};
+use serde_json::json;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::PathBuf;
+
+use super::common;
+use super::Installer;
+use crate::core::agent::{AgentConfig, McpTool, McpTransport};
+use crate::utils::paths::{self, BaseDirs};
+
+/// Installer for Roo Code
+pub struct RooInstaller {
+    /// Whether to install globally
+    global: bool,
+
+    /// Directories paths are resolved against
+    base: BaseDirs,
+}
+
+impl RooInstaller {
+    pub fn new(global: bool, base: BaseDirs) -> Self {
+        Self { global, base }
+    }
+
+    /// Get the rules directory
+    fn get_rules_dir(&self) -> Result<PathBuf> {
+        if self.global {
+            paths::roo_config_dir(&self.base)
+                .map(|dir| dir.join("rules"))
+                .context("Could not find Roo configuration directory")
+        } else {
+            Ok(self.base.project_path(".roo").join("rules"))
+        }
+    }
+
+    /// Get the custom modes file
+    fn get_modes_path(&self) -> Result<PathBuf> {
+        if self.global {
+            paths::roo_settings_dir(&self.base)
+                .map(|dir| dir.join("custom_modes.yaml"))
+                .context("Could not find Roo settings directory")
+        } else {
+            Ok(self.base.project_path(".roomodes"))
+        }
+    }
+
+    /// Get the MCP config path
+    fn get_mcp_config_path(&self) -> Result<PathBuf> {
+        if self.global {
+            paths::roo_settings_dir(&self.base)
+                .map(|dir| dir.join("mcp_settings.json"))
+                .context("Could not find Roo settings directory")
+        } else {
+            Ok(self.base.project_path(".roo").join("mcp.json"))
+        }
+    }
+
+    /// Roo rules are plain Markdown without frontmatter
+    fn rule_frontmatter(_description: &str) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Build the custom mode definition for an agent
+    fn generate_mode(agent: &AgentConfig) -> Value {
+        let icon = agent.identity.icon.as_deref().unwrap_or("🤖");
+
+        let mut mode = Mapping::new();
+        mode.insert("slug".into(), agent.name.clone().into());
+        mode.insert("name".into(), format!("{} {}", icon, agent.name).into());
+        mode.insert("roleDefinition".into(), agent.identity.system_prompt.clone().into());
+        mode.insert("whenToUse".into(), agent.description.clone().into());
+        mode.insert(
+            "groups".into(),
+            Value::Sequence(
+                ["read", "edit", "browser", "command", "mcp"]
+                    .iter()
+                    .map(|group| Value::from(*group))
+                    .collect(),
+            ),
+        );
+
+        Value::Mapping(mode)
+    }
+
+    /// Load the custom modes document (YAML or JSON) and apply `update` to its mode list
+    fn update_modes(&self, update: impl FnOnce(&mut Vec<Value>)) -> Result<()> {
+        let modes_path = self.get_modes_path()?;
+
+        let mut doc: Value = if modes_path.exists() {
+            let content = fs::read_to_string(&modes_path)?;
+            if content.trim().is_empty() {
+                Value::Mapping(Mapping::new())
+            } else {
+                serde_yaml::from_str(&content)
+                    .with_context(|| format!("Failed to parse {}", modes_path.display()))?
+            }
+        } else {
+            Value::Mapping(Mapping::new())
+        };
+
+        let root = doc
+            .as_mapping_mut()
+            .with_context(|| format!("{} is not a mapping", modes_path.display()))?;
+
+        let mut modes = match root.remove("customModes") {
+            Some(Value::Sequence(modes)) => modes,
+            _ => Vec::new(),
+        };
+        update(&mut modes);
+        root.insert("customModes".into(), Value::Sequence(modes));
+
+        if let Some(parent) = modes_path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        common::write_file(&modes_path, serde_yaml::to_string(&doc)?)?;
+
+        Ok(())
+    }
+
+    fn is_mode(mode: &Value, slug: &str) -> bool {
+        mode.get("slug").and_then(Value::as_str) == Some(slug)
+    }
+
+    /// Build the mcp.json entry for a tool
+    fn server_config(tool: &McpTool) -> serde_json::Value {
+        match tool.transport {
+            McpTransport::Stdio => json!({
+                "command": tool.command,
+                "args": tool.args,
+                "env": tool.env
+            }),
+            McpTransport::Http => json!({
+                "type": "streamable-http",
+                "url": tool.remote_url(),
+                "headers": tool.headers
+            }),
+            McpTransport::Sse => json!({
+                "type": "sse",
+                "url": tool.remote_url(),
+                "headers": tool.headers
+            }),
+        }
+    }
+}
+
+impl Installer for RooInstaller {
+    fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
+        common::write_identity_rule(&self.get_rules_dir()?, agent, "md", Self::rule_frontmatter)?;
+
+        // Also expose the agent as a selectable custom mode
+        let mode = Self::generate_mode(agent);
+        self.update_modes(|modes| {
+            match modes.iter_mut().find(|m| Self::is_mode(m, &agent.name)) {
+                Some(existing) => *existing = mode,
+                None => modes.push(mode),
+            }
+        })
+    }
+
+    fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.skills.is_empty() {
+            return Ok(());
+        }
+
+        common::write_skill_rules(&self.get_rules_dir()?, agent, "md", |_, description| {
+            Self::rule_frontmatter(description)
+        })
+    }
+
+    fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.mcp.is_empty() {
+            return Ok(());
+        }
+
+        let servers = agent
+            .mcp
+            .iter()
+            .map(|tool| (tool.name.clone(), Self::server_config(tool)))
+            .collect();
+
+        common::merge_json_servers(&self.get_mcp_config_path()?, "mcpServers", servers)
+    }
+
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        common::remove_rule_files(&self.get_rules_dir()?, agent_name, ".md")?;
+
+        if self.get_modes_path()?.exists() {
+            self.update_modes(|modes| modes.retain(|m| !Self::is_mode(m, agent_name)))?;
+        }
+
+        Ok(())
+    }
+
+    fn env_reference(&self, name: &str) -> Option<String> {
+        // Roo Code expands `${env:VAR}` in mcp.json
+        Some(format!("${{env:{}}}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(name: &str) -> AgentConfig {
+        serde_yaml::from_str(&format!(
+            r#"
+name: {}
+version: 1.0.0
+description: Reviews code
+author: a
+identity:
+  icon: "🔍"
+  system_prompt: Review carefully
+mcp:
+  - name: search
+    command: npx
+    args: ["search-server"]
+  - name: docs
+    transport: http
+    url: https://mcp.example.com/mcp
+  - name: events
+    transport: sse
+    url: https://mcp.example.com/sse
+"#,
+            name
+        ))
+        .unwrap()
+    }
+
+    fn slugs(path: &std::path::Path) -> Vec<String> {
+        let doc: Value = serde_yaml::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        doc["customModes"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .filter_map(|mode| mode.get("slug").and_then(Value::as_str).map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn test_custom_mode_add_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = RooInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        let modes_path = dir.path().join(".roomodes");
+        fs::write(
+            &modes_path,
+            "customModes:\n  - slug: docs-writer\n    name: Docs Writer\n    roleDefinition: Writes docs\n    groups: [read]\n",
+        )
+        .unwrap();
+
+        installer.install_identity(&agent("reviewer")).unwrap();
+        // Reinstalling replaces the mode instead of adding a second one
+        installer.install_identity(&agent("reviewer")).unwrap();
+        assert_eq!(slugs(&modes_path), ["docs-writer", "reviewer"]);
+
+        let doc: Value = serde_yaml::from_str(&fs::read_to_string(&modes_path).unwrap()).unwrap();
+        let mode = &doc["customModes"][1];
+        assert_eq!(mode["name"].as_str(), Some("🔍 reviewer"));
+        assert_eq!(mode["roleDefinition"].as_str(), Some("Review carefully"));
+        assert_eq!(mode["whenToUse"].as_str(), Some("Reviews code"));
+
+        installer.uninstall("reviewer").unwrap();
+        assert_eq!(slugs(&modes_path), ["docs-writer"]);
+        assert!(!dir.path().join(".roo/rules/reviewer-identity.md").exists());
+    }
+
+    #[test]
+    fn test_mcp_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = RooInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        installer.install_tools(&agent("reviewer")).unwrap();
+
+        let config: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join(".roo/mcp.json")).unwrap()).unwrap();
+        let servers = &config["mcpServers"];
+        assert_eq!(servers["search"]["command"], "npx");
+        assert_eq!(servers["search"]["args"], json!(["search-server"]));
+        assert_eq!(servers["docs"]["type"], "streamable-http");
+        assert_eq!(servers["events"]["type"], "sse");
+        assert_eq!(servers["events"]["url"], "https://mcp.example.com/sse");
+    }
+}
@@ -0,0 +1,234 @@
+//! Windsurf Installer
+//!
+//! Installs agent configurations into Windsurf's native format.
+//!
+//! Output structure:
+//! - .windsurf/rules/{name}-identity.md - Agent identity as a workspace rule
+//! - .windsurf/rules/{name}-{skill}.md - Agent skills as workspace rules
+//! - ~/.codeium/windsurf/memories/global_rules.md - Identity and skills (global installs)
+//! - ~/.codeium/windsurf/mcp_config.json - MCP tool configuration
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use super::common;
+use super::Installer;
+use crate::core::agent::{AgentConfig, McpTool, McpTransport};
+use crate::utils::paths::{self, BaseDirs};
+
+/// Installer for Windsurf
+pub struct WindsurfInstaller {
+    /// Whether to install globally
+    global: bool,
+
+    /// Directories paths are resolved against
+    base: BaseDirs,
+}
+
+impl WindsurfInstaller {
+    pub fn new(global: bool, base: BaseDirs) -> Self {
+        Self { global, base }
+    }
+
+    /// Get the Windsurf configuration directory (~/.codeium/windsurf)
+    fn get_config_dir(&self) -> Result<PathBuf> {
+        paths::windsurf_config_dir(&self.base)
+            .context("Could not find Windsurf configuration directory")
+    }
+
+    /// Get the workspace rules directory (.windsurf/rules)
+    fn get_rules_dir(&self) -> PathBuf {
+        self.base.project_path(".windsurf").join("rules")
+    }
+
+    /// Get the global rules file (~/.codeium/windsurf/memories/global_rules.md)
+    fn get_global_rules_path(&self) -> Result<PathBuf> {
+        Ok(self.get_config_dir()?.join("memories").join("global_rules.md"))
+    }
+
+    /// Get the MCP config path (Windsurf only reads a global MCP config)
+    fn get_mcp_config_path(&self) -> Result<PathBuf> {
+        Ok(self.get_config_dir()?.join("mcp_config.json"))
+    }
+
+    /// Generate Windsurf's activation frontmatter for a workspace rule
+    fn rule_frontmatter(description: &str) -> Vec<(&'static str, String)> {
+        vec![
+            ("trigger", "always_on".to_string()),
+            ("description", description.to_string()),
+        ]
+    }
+
+    /// Build the mcp_config.json entry for a tool (remote servers use `serverUrl`)
+    fn server_config(tool: &McpTool) -> Value {
+        match tool.transport {
+            McpTransport::Stdio => json!({
+                "command": tool.command,
+                "args": tool.args,
+                "env": tool.env
+            }),
+            McpTransport::Http | McpTransport::Sse => json!({
+                "serverUrl": tool.remote_url(),
+                "headers": tool.headers
+            }),
+        }
+    }
+}
+
+impl Installer for WindsurfInstaller {
+    fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
+        if self.global {
+            let body = format!(
+                "# {}\n\n{}",
+                common::identity_title(agent),
+                agent.identity.system_prompt
+            );
+            return common::write_managed_block(&self.get_global_rules_path()?, &agent.name, &body);
+        }
+
+        common::write_identity_rule(&self.get_rules_dir(), agent, "md", Self::rule_frontmatter)
+    }
+
+    fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.skills.is_empty() {
+            return Ok(());
+        }
+
+        if self.global {
+            let rules_path = self.get_global_rules_path()?;
+            for skill in &agent.skills {
+                let body = format!("# {}\n\n{}", common::skill_title(agent, skill), skill.content);
+                common::write_managed_block(
+                    &rules_path,
+                    &format!("{}/{}", agent.name, skill.name),
+                    &body,
+                )?;
+            }
+            return Ok(());
+        }
+
+        common::write_skill_rules(&self.get_rules_dir(), agent, "md", |_, description| {
+            Self::rule_frontmatter(description)
+        })
+    }
+
+    fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.mcp.is_empty() {
+            return Ok(());
+        }
+
+        let servers = agent
+            .mcp
+            .iter()
+            .map(|tool| (tool.name.clone(), Self::server_config(tool)))
+            .collect();
+
+        common::merge_json_servers(&self.get_mcp_config_path()?, "mcpServers", servers)
+    }
+
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        if self.global {
+            return common::clear_managed_blocks(&self.get_global_rules_path()?, agent_name);
+        }
+
+        common::remove_rule_files(&self.get_rules_dir(), agent_name, ".md")?;
+
+        Ok(())
+    }
+
+    fn env_reference(&self, name: &str) -> Option<String> {
+        // Windsurf expands `${env:VAR}` in mcp_config.json
+        Some(format!("${{env:{}}}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn agent(name: &str) -> AgentConfig {
+        serde_yaml::from_str(&format!(
+            "name: {}\nversion: 1.0.0\ndescription: Cleans code\nauthor: a\nidentity:\n  system_prompt: Be tidy\nskills:\n  - name: lint\n    content: Run the linter\n",
+            name
+        ))
+        .unwrap()
+    }
+
+    fn install(installer: &WindsurfInstaller, agent: &AgentConfig) {
+        installer.install_identity(agent).unwrap();
+        installer.install_skills(agent).unwrap();
+    }
+
+    #[test]
+    fn test_workspace_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = WindsurfInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        install(&installer, &agent("code"));
+
+        let rules_dir = dir.path().join(".windsurf/rules");
+        assert_eq!(
+            fs::read_to_string(rules_dir.join("code-identity.md")).unwrap(),
+            "---\ntrigger: always_on\ndescription: Cleans code\n---\n\n# 🤖 code Agent\n\nBe tidy\n<!-- ax:agent code -->\n"
+        );
+        assert!(fs::read_to_string(rules_dir.join("code-lint.md"))
+            .unwrap()
+            .contains("# code - lint\n\nRun the linter"));
+    }
+
+    #[test]
+    fn test_uninstall_keeps_other_agents() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = WindsurfInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        install(&installer, &agent("code"));
+        install(&installer, &agent("code-cleaner"));
+
+        let rules_dir = dir.path().join(".windsurf/rules");
+        fs::write(rules_dir.join("code-notes.md"), "Written by hand").unwrap();
+
+        installer.uninstall("code").unwrap();
+        let mut left: Vec<String> = fs::read_dir(&rules_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["code-cleaner-identity.md", "code-cleaner-lint.md", "code-notes.md"]);
+    }
+
+    #[test]
+    fn test_uninstall_removes_unmarked_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = WindsurfInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        install(&installer, &agent("code-cleaner"));
+
+        // Rule files written before the owner marker was added
+        let rules_dir = dir.path().join(".windsurf/rules");
+        fs::write(rules_dir.join("code-identity.md"), "# 🤖 code Agent\n\nhi\n").unwrap();
+        fs::write(rules_dir.join("code-lint.md"), "# code - lint\n\nRun the linter\n").unwrap();
+        fs::write(rules_dir.join("code-notes.md"), "# Notes\n").unwrap();
+
+        installer.uninstall("code").unwrap();
+        let mut left: Vec<String> = fs::read_dir(&rules_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["code-cleaner-identity.md", "code-cleaner-lint.md", "code-notes.md"]);
+    }
+
+    #[test]
+    fn test_global_rules_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = WindsurfInstaller::new(true, BaseDirs::new(dir.path(), dir.path().join("project")));
+        install(&installer, &agent("code"));
+
+        let rules_path = dir.path().join(".codeium/windsurf/memories/global_rules.md");
+        let content = fs::read_to_string(&rules_path).unwrap();
+        assert!(content.contains("<!-- ax:begin code -->"));
+        assert!(content.contains("<!-- ax:begin code/lint -->"));
+
+        installer.uninstall("code").unwrap();
+        assert!(fs::read_to_string(&rules_path).unwrap().trim().is_empty());
+    }
+}
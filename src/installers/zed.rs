@@ -0,0 +1,198 @@
+//! Zed Installer
+//!
+//! Installs agent configurations into Zed's native format.
+//!
+//! Output structure:
+//! - .rules - Agent identity and skills as managed blocks
+//! - .zed/settings.json - MCP tool configuration (`context_servers`)
+//!
+//! Zed keeps global rules in its Rules Library rather than on disk, so
+//! global installs only configure MCP servers in ~/.config/zed/settings.json.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use super::common;
+use super::Installer;
+use crate::core::agent::{AgentConfig, McpTool, McpTransport};
+use crate::utils::paths::{self, BaseDirs};
+use crate::utils::ui;
+
+/// Installer for Zed
+pub struct ZedInstaller {
+    /// Whether to install globally
+    global: bool,
+
+    /// Directories paths are resolved against
+    base: BaseDirs,
+}
+
+impl ZedInstaller {
+    pub fn new(global: bool, base: BaseDirs) -> Self {
+        Self { global, base }
+    }
+
+    /// Get the project rules file
+    fn get_rules_path(&self) -> PathBuf {
+        self.base.project_path(".rules")
+    }
+
+    /// Get the settings path holding context servers
+    fn get_settings_path(&self) -> Result<PathBuf> {
+        if self.global {
+            paths::zed_config_dir(&self.base)
+                .map(|dir| dir.join("settings.json"))
+                .context("Could not find Zed configuration directory")
+        } else {
+            Ok(self.base.project_path(".zed").join("settings.json"))
+        }
+    }
+
+    fn warn_no_global_rules(&self) {
+        ui::print_warning("Zed has no global rules file; use the Rules Library or install without --global");
+    }
+
+    /// Build the `context_servers` entry for a tool (Zed has no SSE support)
+    fn server_config(tool: &McpTool) -> Result<Value> {
+        match tool.transport {
+            McpTransport::Stdio => Ok(json!({
+                "source": "custom",
+                "command": tool.command,
+                "args": tool.args,
+                "env": tool.env
+            })),
+            McpTransport::Http => Ok(json!({
+                "url": tool.remote_url(),
+                "headers": tool.headers
+            })),
+            McpTransport::Sse => Err(common::unsupported_transport("Zed", tool)),
+        }
+    }
+}
+
+impl Installer for ZedInstaller {
+    fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
+        if self.global {
+            self.warn_no_global_rules();
+            return Ok(());
+        }
+
+        let body = format!(
+            "# {}\n\n{}",
+            common::identity_title(agent),
+            agent.identity.system_prompt
+        );
+        common::write_managed_block(&self.get_rules_path(), &agent.name, &body)
+    }
+
+    fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.skills.is_empty() || self.global {
+            return Ok(());
+        }
+
+        // Zed reads a single rules file, so each skill becomes its own block
+        let rules_path = self.get_rules_path();
+        for skill in &agent.skills {
+            let body = format!("# {}\n\n{}", common::skill_title(agent, skill), skill.content);
+            common::write_managed_block(&rules_path, &format!("{}/{}", agent.name, skill.name), &body)?;
+        }
+
+        Ok(())
+    }
+
+    fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.mcp.is_empty() {
+            return Ok(());
+        }
+
+        let servers = agent
+            .mcp
+            .iter()
+            .map(|tool| Ok((tool.name.clone(), Self::server_config(tool)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        common::merge_json_servers(&self.get_settings_path()?, "context_servers", servers)
+            .context("Zed settings must be plain JSON (without comments) for ax to update them")
+    }
+
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        if !self.global {
+            common::clear_managed_blocks(&self.get_rules_path(), agent_name)?;
+        }
+
+        Ok(())
+    }
+
+    fn mcp_config_path(&self) -> Result<Option<PathBuf>> {
+        // Zed reads settings.json as is, without expanding variables
+        self.get_settings_path().map(Some)
+    }
+
+    fn installs_identity(&self) -> bool {
+        !self.global
+    }
+
+    fn installs_skills(&self) -> bool {
+        !self.global
+    }
+
+    fn supports_transport(&self, transport: McpTransport) -> bool {
+        transport != McpTransport::Sse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn agent(mcp: &str) -> AgentConfig {
+        serde_yaml::from_str(&format!(
+            "name: demo\nversion: 1.0.0\ndescription: d\nauthor: a\nidentity:\n  system_prompt: Be helpful\nskills:\n  - name: lint\n    content: Run the linter\nmcp:\n{}",
+            mcp
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_settings_merge_keeps_other_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = ZedInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        let settings_path = dir.path().join(".zed/settings.json");
+        fs::create_dir_all(settings_path.parent().unwrap()).unwrap();
+        fs::write(&settings_path, r#"{"tab_size": 4, "context_servers": {"existing": {"command": "uvx"}}}"#).unwrap();
+
+        let mcp = "  - name: search\n    command: npx\n  - name: docs\n    transport: http\n    url: https://mcp.example.com/mcp\n";
+        installer.install_tools(&agent(mcp)).unwrap();
+
+        let settings: Value = serde_json::from_str(&fs::read_to_string(&settings_path).unwrap()).unwrap();
+        assert_eq!(settings["tab_size"], 4);
+        assert_eq!(settings["context_servers"]["existing"]["command"], "uvx");
+        assert_eq!(settings["context_servers"]["search"]["source"], "custom");
+        assert_eq!(settings["context_servers"]["search"]["command"], "npx");
+        assert_eq!(settings["context_servers"]["docs"]["url"], "https://mcp.example.com/mcp");
+
+        // SSE servers are refused without touching the file
+        let before = fs::read_to_string(&settings_path).unwrap();
+        let sse = "  - name: events\n    transport: sse\n    url: https://mcp.example.com/sse\n";
+        assert!(installer.install_tools(&agent(sse)).is_err());
+        assert_eq!(fs::read_to_string(&settings_path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_rules_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = ZedInstaller::new(false, BaseDirs::new(dir.path().join("home"), dir.path()));
+        let agent = agent("  []\n");
+
+        installer.install_identity(&agent).unwrap();
+        installer.install_skills(&agent).unwrap();
+        let rules = fs::read_to_string(dir.path().join(".rules")).unwrap();
+        assert!(rules.contains("<!-- ax:begin demo -->\n# 🤖 demo Agent\n\nBe helpful\n<!-- ax:end demo -->"));
+        assert!(rules.contains("<!-- ax:begin demo/lint -->"));
+
+        installer.uninstall("demo").unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join(".rules")).unwrap(), "");
+    }
+}
@@ -4,17 +4,63 @@ use anyhow::Result;
 use clap::Parser;
 
 use ax_lib::cli::{Cli, Commands};
+use ax_lib::core::{cache, error};
+use ax_lib::utils::output;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     let cli = Cli::parse();
+    output::set_format(cli.output);
+    cache::set_offline(cli.offline);
 
-    match cli.command {
+    if let Err(err) = run(cli.command).await {
+        if output::is_json() {
+            output::emit_error(&err).ok();
+        } else {
+            eprintln!("Error: {:?}", err);
+        }
+        std::process::exit(error::exit_code(&err));
+    }
+}
+
+async fn run(command: Commands) -> Result<()> {
+    match command {
         Commands::Init => ax_lib::cli::commands::init::execute().await,
         Commands::List => ax_lib::cli::commands::list::execute().await,
-        Commands::Install { agent, target, global } => {
-            ax_lib::cli::commands::install::execute(&agent, target, global).await
+        Commands::Search {
+            query,
+            author,
+            target_compatible,
+        } => ax_lib::cli::commands::search::execute(&query, author, target_compatible).await,
+        Commands::Install {
+            agent,
+            target,
+            global,
+            yes,
+            env,
+            env_file,
+            require_env,
+            plaintext_secrets,
+            strict_deps,
+        } => {
+            let options = ax_lib::cli::commands::install::EnvOptions {
+                yes,
+                env,
+                env_files: env_file,
+                require_env,
+                plaintext_secrets,
+            };
+            ax_lib::cli::commands::install::execute(&agent, &target, global, strict_deps, &options)
+                .await
         }
+        Commands::Cache { action } => ax_lib::cli::commands::cache::execute(action).await,
+        Commands::Migrate { path, dry_run } => {
+            ax_lib::cli::commands::migrate::execute(&path, dry_run).await
+        }
+        Commands::Schema { kind } => ax_lib::cli::commands::schema::execute(kind).await,
+        Commands::Info { agent, target } => {
+            ax_lib::cli::commands::info::execute(&agent, target).await
+        }
+        Commands::Tree { agent } => ax_lib::cli::commands::tree::execute(&agent).await,
     }
 }
-
@@ -0,0 +1,77 @@
+//! Editor Detection
+//!
+//! Checks which supported editors are installed on this machine.
+
+use std::path::PathBuf;
+
+use crate::installers::Target;
+use crate::utils::paths::{self, BaseDirs};
+
+/// Check whether the editor for a target appears to be installed
+pub fn is_installed(target: Target) -> bool {
+    let base = BaseDirs::system();
+    match target {
+        Target::Claude => exists(paths::claude_config_dir(&base)),
+        Target::Cursor => paths::cursor_config_dir(&base)
+            .map(|path| path.exists())
+            .unwrap_or_else(|| {
+                // Also check for .cursor in current directory
+                PathBuf::from(".cursor").exists()
+            }),
+        Target::Codex => exists(paths::codex_config_dir(&base)) || on_path("codex"),
+        Target::Windsurf => {
+            exists(paths::windsurf_config_dir(&base)) || PathBuf::from(".windsurf").exists()
+        }
+        // Check if code command exists
+        Target::Copilot => on_path("code"),
+        Target::Gemini => exists(paths::gemini_config_dir(&base)) || on_path("gemini"),
+        Target::Cline => {
+            exists(paths::cline_settings_dir(&base).and_then(|p| p.parent().map(PathBuf::from)))
+                || PathBuf::from(".clinerules").exists()
+        }
+        Target::Roo => {
+            exists(paths::roo_settings_dir(&base).and_then(|p| p.parent().map(PathBuf::from)))
+                || PathBuf::from(".roo").exists()
+        }
+        Target::Zed => exists(paths::zed_config_dir(&base)) || on_path("zed"),
+        Target::Continue => {
+            exists(paths::continue_config_dir(&base)) || PathBuf::from(".continue").exists()
+        }
+        Target::Opencode => exists(paths::opencode_config_dir(&base)) || on_path("opencode"),
+    }
+}
+
+/// Get the configuration directory shown for a detected target
+pub fn config_dir(target: Target) -> Option<PathBuf> {
+    let base = BaseDirs::system();
+    match target {
+        Target::Claude => paths::claude_config_dir(&base),
+        Target::Cursor => paths::cursor_config_dir(&base),
+        Target::Codex => paths::codex_config_dir(&base),
+        Target::Windsurf => paths::windsurf_config_dir(&base),
+        Target::Copilot => paths::vscode_config_dir(&base),
+        Target::Gemini => paths::gemini_config_dir(&base),
+        Target::Cline => paths::cline_settings_dir(&base),
+        Target::Roo => paths::roo_settings_dir(&base),
+        Target::Zed => paths::zed_config_dir(&base),
+        Target::Continue => paths::continue_config_dir(&base),
+        Target::Opencode => paths::opencode_config_dir(&base),
+    }
+}
+
+/// Get every target whose editor is installed, in [`Target::ALL`] order
+pub fn detected_targets() -> Vec<Target> {
+    Target::ALL
+        .iter()
+        .copied()
+        .filter(|target| is_installed(*target))
+        .collect()
+}
+
+fn exists(path: Option<PathBuf>) -> bool {
+    path.map(|path| path.exists()).unwrap_or(false)
+}
+
+fn on_path(command: &str) -> bool {
+    which::which(command).is_ok()
+}
@@ -1,5 +1,7 @@
 //! Utilities Module
 
+pub mod detect;
+pub mod output;
 pub mod paths;
 pub mod ui;
 pub mod validation;
@@ -0,0 +1,120 @@
+//! Output Format
+//!
+//! Commands print colored text by default. With `--output json`, text,
+//! spinners and prompts are suppressed and each command prints a single
+//! JSON document to stdout instead, so ax can be driven by scripts.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use crate::core::error;
+
+/// Output format selected with `--output`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable, colored text
+    #[default]
+    Text,
+    /// A single JSON document on stdout
+    Json,
+}
+
+static FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+static EMITTED: AtomicBool = AtomicBool::new(false);
+
+// Commands install and report on one thread, so writes and warnings are
+// collected per thread; concurrent runs (such as tests) don't see each
+// other's files.
+thread_local! {
+    static WRITTEN: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+    static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Select the output format for this run
+pub fn set_format(format: OutputFormat) {
+    FORMAT.get_or_init(|| format);
+}
+
+/// Whether JSON output was requested
+pub fn is_json() -> bool {
+    FORMAT.get() == Some(&OutputFormat::Json)
+}
+
+/// Print the command's JSON result to stdout
+pub fn emit<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    EMITTED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Print an error as JSON, unless the command already reported its result
+pub fn emit_error(err: &anyhow::Error) -> Result<()> {
+    if EMITTED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+
+    emit(&serde_json::json!({
+        "error": ErrorReport::new(err),
+        "warnings": take_warnings(),
+    }))
+}
+
+/// An error in a JSON report
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorReport {
+    /// Process exit code for this kind of failure
+    pub code: i32,
+    /// The error and its causes, outermost first
+    pub message: String,
+}
+
+impl ErrorReport {
+    pub fn new(err: &anyhow::Error) -> Self {
+        Self {
+            code: error::exit_code(err),
+            message: format!("{:#}", err),
+        }
+    }
+}
+
+/// Record a file written by an installer
+pub fn record_write(path: &Path) {
+    WRITTEN.with(|written| written.borrow_mut().push(path.to_path_buf()));
+}
+
+/// Files written since the last call, in write order without repeats
+pub fn take_written() -> Vec<PathBuf> {
+    let written = WRITTEN.with(|written| written.take());
+    let mut files = Vec::new();
+    for path in written {
+        if !files.contains(&path) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Record a warning for the JSON report
+pub fn record_warning(message: &str) {
+    WARNINGS.with(|warnings| warnings.borrow_mut().push(message.to_string()));
+}
+
+/// Warnings recorded so far
+pub fn take_warnings() -> Vec<String> {
+    WARNINGS.with(|warnings| warnings.take())
+}
+
+/// `println!` for human-readable output, suppressed with `--output json`
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !$crate::utils::output::is_json() {
+            println!($($arg)*);
+        }
+    };
+}
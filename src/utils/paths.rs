@@ -3,7 +3,7 @@
 //! Cross-platform path resolution for configuration directories.
 
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Get the AX configuration directory (~/.ax)
 pub fn ax_config_dir() -> Result<PathBuf> {
@@ -16,37 +16,123 @@ pub fn ax_config_path() -> Result<PathBuf> {
     Ok(ax_config_dir()?.join("config.toml"))
 }
 
+/// Get the AX content cache directory (~/.ax/cache)
+pub fn ax_cache_dir() -> Result<PathBuf> {
+    Ok(ax_config_dir()?.join("cache"))
+}
+
+/// Get the AX secrets file path (~/.ax/secrets.toml)
+pub fn ax_secrets_path() -> Result<PathBuf> {
+    Ok(ax_config_dir()?.join("secrets.toml"))
+}
+
+/// Get the project configuration file path (ax.toml in the current directory)
+pub fn project_config_path() -> PathBuf {
+    PathBuf::from("ax.toml")
+}
+
+/// Get the AX installer plugins directory (~/.ax/plugins)
+pub fn ax_plugins_dir() -> Result<PathBuf> {
+    Ok(ax_config_dir()?.join("plugins"))
+}
+
+/// Home and project directories that editor paths are resolved against
+///
+/// The default uses the real home directory and the current working
+/// directory. `ax info --target` and tests point both into a scratch
+/// directory instead, without touching the process environment.
+#[derive(Debug, Clone, Default)]
+pub struct BaseDirs {
+    home: Option<PathBuf>,
+    project: Option<PathBuf>,
+}
+
+impl BaseDirs {
+    /// Resolve against the user's home and the current directory
+    pub fn system() -> Self {
+        Self::default()
+    }
+
+    /// Resolve against explicit directories
+    ///
+    /// The config and documents directories become `home/.config` and
+    /// `home/Documents`, whatever the platform.
+    pub fn new(home: impl Into<PathBuf>, project: impl Into<PathBuf>) -> Self {
+        Self {
+            home: Some(home.into()),
+            project: Some(project.into()),
+        }
+    }
+
+    /// The home directory
+    pub fn home_dir(&self) -> Option<PathBuf> {
+        match &self.home {
+            Some(home) => Some(home.clone()),
+            None => dirs::home_dir(),
+        }
+    }
+
+    /// The user configuration directory (e.g. ~/.config)
+    pub fn config_dir(&self) -> Option<PathBuf> {
+        match &self.home {
+            Some(home) => Some(home.join(".config")),
+            None => dirs::config_dir(),
+        }
+    }
+
+    /// The documents directory (e.g. ~/Documents)
+    pub fn document_dir(&self) -> Option<PathBuf> {
+        match &self.home {
+            Some(home) => Some(home.join("Documents")),
+            None => dirs::document_dir(),
+        }
+    }
+
+    /// The project directory, if it isn't the current directory
+    pub fn project_dir(&self) -> Option<&Path> {
+        self.project.as_deref()
+    }
+
+    /// A path inside the project (relative to the current directory by default)
+    pub fn project_path(&self, path: impl AsRef<Path>) -> PathBuf {
+        match &self.project {
+            Some(project) => project.join(path),
+            None => path.as_ref().to_path_buf(),
+        }
+    }
+}
+
 /// Get the Claude configuration directory
 /// 
 /// On macOS: ~/Library/Application Support/Claude
 /// On Linux: ~/.config/claude or ~/.claude
 /// On Windows: %APPDATA%/Claude
-pub fn claude_config_dir() -> Option<PathBuf> {
+pub fn claude_config_dir(base: &BaseDirs) -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
     {
-        dirs::home_dir().map(|h| h.join("Library/Application Support/Claude"))
+        base.home_dir().map(|h| h.join("Library/Application Support/Claude"))
     }
 
     #[cfg(target_os = "linux")]
     {
         // Try XDG config first, then fallback to ~/.claude
-        if let Some(config) = dirs::config_dir() {
+        if let Some(config) = base.config_dir() {
             let claude_dir = config.join("claude");
             if claude_dir.exists() {
                 return Some(claude_dir);
             }
         }
-        dirs::home_dir().map(|h| h.join(".claude"))
+        base.home_dir().map(|h| h.join(".claude"))
     }
 
     #[cfg(target_os = "windows")]
     {
-        dirs::config_dir().map(|d| d.join("Claude"))
+        base.config_dir().map(|d| d.join("Claude"))
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     {
-        dirs::home_dir().map(|h| h.join(".claude"))
+        base.home_dir().map(|h| h.join(".claude"))
     }
 }
 
@@ -55,43 +141,43 @@ pub fn claude_config_dir() -> Option<PathBuf> {
 /// On macOS: ~/Library/Application Support/Cursor
 /// On Linux: ~/.config/Cursor
 /// On Windows: %APPDATA%/Cursor
-pub fn cursor_config_dir() -> Option<PathBuf> {
+pub fn cursor_config_dir(base: &BaseDirs) -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
     {
-        dirs::home_dir().map(|h| h.join("Library/Application Support/Cursor"))
+        base.home_dir().map(|h| h.join("Library/Application Support/Cursor"))
     }
 
     #[cfg(target_os = "linux")]
     {
-        dirs::config_dir().map(|d| d.join("Cursor"))
+        base.config_dir().map(|d| d.join("Cursor"))
     }
 
     #[cfg(target_os = "windows")]
     {
-        dirs::config_dir().map(|d| d.join("Cursor"))
+        base.config_dir().map(|d| d.join("Cursor"))
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     {
-        dirs::home_dir().map(|h| h.join(".cursor"))
+        base.home_dir().map(|h| h.join(".cursor"))
     }
 }
 
 /// Get the VS Code configuration directory
-pub fn vscode_config_dir() -> Option<PathBuf> {
+pub fn vscode_config_dir(base: &BaseDirs) -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
     {
-        dirs::home_dir().map(|h| h.join("Library/Application Support/Code"))
+        base.home_dir().map(|h| h.join("Library/Application Support/Code"))
     }
 
     #[cfg(target_os = "linux")]
     {
-        dirs::config_dir().map(|d| d.join("Code"))
+        base.config_dir().map(|d| d.join("Code"))
     }
 
     #[cfg(target_os = "windows")]
     {
-        dirs::config_dir().map(|d| d.join("Code"))
+        base.config_dir().map(|d| d.join("Code"))
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
@@ -100,36 +186,124 @@ pub fn vscode_config_dir() -> Option<PathBuf> {
     }
 }
 
+/// Get the Windsurf configuration directory
+///
+/// On all platforms: ~/.codeium/windsurf
+pub fn windsurf_config_dir(base: &BaseDirs) -> Option<PathBuf> {
+    base.home_dir().map(|h| h.join(".codeium").join("windsurf"))
+}
+
+/// Get the Gemini CLI configuration directory
+///
+/// On all platforms: ~/.gemini
+pub fn gemini_config_dir(base: &BaseDirs) -> Option<PathBuf> {
+    base.home_dir().map(|h| h.join(".gemini"))
+}
+
+/// Get the VS Code global storage directory used by extensions
+pub fn vscode_global_storage_dir(base: &BaseDirs) -> Option<PathBuf> {
+    vscode_config_dir(base).map(|d| d.join("User").join("globalStorage"))
+}
+
+/// Get the Cline extension settings directory (inside VS Code global storage)
+pub fn cline_settings_dir(base: &BaseDirs) -> Option<PathBuf> {
+    vscode_global_storage_dir(base).map(|d| d.join("saoudrizwan.claude-dev").join("settings"))
+}
+
+/// Get the Cline global rules directory
+///
+/// On all platforms: ~/Documents/Cline/Rules
+pub fn cline_global_rules_dir(base: &BaseDirs) -> Option<PathBuf> {
+    base.document_dir()
+        .or_else(|| base.home_dir().map(|h| h.join("Documents")))
+        .map(|d| d.join("Cline").join("Rules"))
+}
+
+/// Get the Roo Code extension settings directory (inside VS Code global storage)
+pub fn roo_settings_dir(base: &BaseDirs) -> Option<PathBuf> {
+    vscode_global_storage_dir(base).map(|d| d.join("rooveterinaryinc.roo-cline").join("settings"))
+}
+
+/// Get the Roo Code global configuration directory
+///
+/// On all platforms: ~/.roo
+pub fn roo_config_dir(base: &BaseDirs) -> Option<PathBuf> {
+    base.home_dir().map(|h| h.join(".roo"))
+}
+
+/// Get the Zed configuration directory
+///
+/// On macOS/Linux: ~/.config/zed (respecting XDG_CONFIG_HOME on Linux)
+/// On Windows: %APPDATA%/Zed
+pub fn zed_config_dir(base: &BaseDirs) -> Option<PathBuf> {
+    #[cfg(target_os = "linux")]
+    {
+        base.config_dir().map(|d| d.join("zed"))
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        base.config_dir().map(|d| d.join("Zed"))
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    {
+        base.home_dir().map(|h| h.join(".config").join("zed"))
+    }
+}
+
+/// Get the Continue configuration directory
+///
+/// On all platforms: ~/.continue
+pub fn continue_config_dir(base: &BaseDirs) -> Option<PathBuf> {
+    base.home_dir().map(|h| h.join(".continue"))
+}
+
+/// Get the opencode configuration directory
+///
+/// On all platforms: ~/.config/opencode (respecting XDG_CONFIG_HOME on Linux)
+pub fn opencode_config_dir(base: &BaseDirs) -> Option<PathBuf> {
+    #[cfg(target_os = "linux")]
+    {
+        base.config_dir().map(|d| d.join("opencode"))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        base.home_dir().map(|h| h.join(".config").join("opencode"))
+    }
+}
+
 /// Get the Codex configuration directory
 ///
 /// On macOS: ~/Library/Application Support/Codex
 /// On Linux: ~/.config/codex or ~/.codex
 /// On Windows: %APPDATA%/Codex
-pub fn codex_config_dir() -> Option<PathBuf> {
+pub fn codex_config_dir(base: &BaseDirs) -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
     {
-        dirs::home_dir().map(|h| h.join("Library/Application Support/Codex"))
+        base.home_dir().map(|h| h.join("Library/Application Support/Codex"))
     }
 
     #[cfg(target_os = "linux")]
     {
         // Try XDG config first, then fallback to ~/.codex
-        if let Some(config) = dirs::config_dir() {
+        if let Some(config) = base.config_dir() {
             let codex_dir = config.join("codex");
             if codex_dir.exists() {
                 return Some(codex_dir);
             }
         }
-        dirs::home_dir().map(|h| h.join(".codex"))
+        base.home_dir().map(|h| h.join(".codex"))
     }
 
     #[cfg(target_os = "windows")]
     {
-        dirs::config_dir().map(|d| d.join("Codex"))
+        base.config_dir().map(|d| d.join("Codex"))
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     {
-        dirs::home_dir().map(|h| h.join(".codex"))
+        base.home_dir().map(|h| h.join(".codex"))
     }
 }
@@ -5,30 +5,41 @@
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 
+use crate::say;
+use crate::utils::output;
+
 /// Print a styled header
 pub fn print_header(text: &str) {
-    println!();
-    println!("  {} {}", "▶".cyan().bold(), text.bold());
-    println!();
+    say!();
+    say!("  {} {}", "▶".cyan().bold(), text.bold());
+    say!();
 }
 
 /// Print a success message
 pub fn print_success(text: &str) {
-    println!("  {} {}", "✓".green().bold(), text.green());
+    say!("  {} {}", "✓".green().bold(), text.green());
 }
 
-/// Print a warning message
+/// Print a warning message (included in the report with `--output json`)
 pub fn print_warning(text: &str) {
-    println!("  {} {}", "⚠".yellow().bold(), text.yellow());
+    if output::is_json() {
+        output::record_warning(text);
+    } else {
+        println!("  {} {}", "⚠".yellow().bold(), text.yellow());
+    }
 }
 
 /// Print an error message
 pub fn print_error(text: &str) {
-    println!("  {} {}", "✗".red().bold(), text.red());
+    say!("  {} {}", "✗".red().bold(), text.red());
 }
 
 /// Create a spinner progress bar
 pub fn create_spinner(message: &str) -> ProgressBar {
+    if output::is_json() {
+        return ProgressBar::hidden();
+    }
+
     let pb = ProgressBar::new_spinner();
     pb.set_style(
         ProgressStyle::with_template("  {spinner:.cyan} {msg}")
@@ -42,6 +53,10 @@ pub fn create_spinner(message: &str) -> ProgressBar {
 
 /// Create a progress bar with known length
 pub fn create_progress_bar(len: u64, message: &str) -> ProgressBar {
+    if output::is_json() {
+        return ProgressBar::hidden();
+    }
+
     let pb = ProgressBar::new(len);
     pb.set_style(
         ProgressStyle::with_template(
@@ -56,10 +71,19 @@ pub fn create_progress_bar(len: u64, message: &str) -> ProgressBar {
 
 /// Print a key-value pair
 pub fn print_kv(key: &str, value: &str) {
-    println!("  {}: {}", key.dimmed(), value);
+    say!("  {}: {}", key.dimmed(), value);
+}
+
+/// Truncate and pad text to a column of `width` terminal cells
+///
+/// Works on display width, so multi-byte and wide characters neither panic
+/// nor break the table alignment.
+pub fn fit_column(text: &str, width: usize) -> String {
+    let text = console::truncate_str(text, width, "...");
+    console::pad_str(&text, width, console::Alignment::Left, None).into_owned()
 }
 
 /// Print a bullet point
 pub fn print_bullet(text: &str) {
-    println!("  {} {}", "•".dimmed(), text);
+    say!("  {} {}", "•".dimmed(), text);
 }
@@ -1,8 +1,25 @@
 //! Validation Utilities
 //!
-//! Checks for required tool dependencies.
+//! Checks for required tool dependencies: MCP server commands, the tools a
+//! skill lists in `dependencies` or `compatibility`, and the interpreters
+//! named in the shebangs of its scripts.
 
-use crate::core::agent::AgentConfig;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::core::agent::{AgentConfig, McpTransport, Skill};
+use crate::installers;
+
+/// A tool an agent needs that isn't on PATH
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingDependency {
+    /// Command name (e.g. "ruff")
+    pub tool: String,
+    /// What needs it (e.g. "skill 'python-lint'")
+    pub required_by: Vec<String>,
+    /// How to install it
+    pub hint: String,
+}
 
 /// Check if a tool is available in PATH
 pub fn is_tool_available(name: &str) -> bool {
@@ -10,32 +27,188 @@ pub fn is_tool_available(name: &str) -> bool {
 }
 
 /// Check agent dependencies and return missing tools
-pub fn check_agent_dependencies(agent: &AgentConfig) -> Vec<String> {
-    let mut missing = Vec::new();
+///
+/// Remote skills' scripts are fetched through the cache to read their
+/// shebangs, so this blocks on the network.
+pub fn check_agent_dependencies(agent: &AgentConfig) -> Vec<MissingDependency> {
+    let mut missing: Vec<MissingDependency> = Vec::new();
 
-    for tool in &agent.mcp {
-        // Check if the command exists
-        if !is_tool_available(&tool.command) {
-            missing.push(tool.command.clone());
+    for (tool, required_by) in required_tools(agent, installers::skill_scripts) {
+        if is_tool_available(&tool) {
+            continue;
+        }
+        match missing.iter_mut().find(|dep| dep.tool == tool) {
+            Some(dep) if !dep.required_by.contains(&required_by) => dep.required_by.push(required_by),
+            Some(_) => {}
+            None => missing.push(MissingDependency {
+                hint: remediation(&tool),
+                tool,
+                required_by: vec![required_by],
+            }),
         }
     }
 
     missing
 }
 
+/// Every tool an agent needs, with what needs it
+fn required_tools(
+    agent: &AgentConfig,
+    scripts: impl Fn(&Skill) -> Vec<(String, String)>,
+) -> Vec<(String, String)> {
+    let mut tools = Vec::new();
+
+    // Remote (http/sse) servers don't run a local command
+    for tool in agent.mcp.iter().filter(|tool| tool.transport == McpTransport::Stdio) {
+        tools.push((tool.command.clone(), format!("MCP tool '{}'", tool.name)));
+    }
+
+    for skill in &agent.skills {
+        let by = format!("skill '{}'", skill.name);
+        let listed = skill.dependencies.as_deref().map(parse_dependencies).unwrap_or_default();
+        let compatible = skill.compatibility.as_deref().map(parse_compatibility).unwrap_or_default();
+        tools.extend(listed.into_iter().chain(compatible).map(|tool| (tool, by.clone())));
+
+        for (file, content) in scripts(skill) {
+            if let Some(interpreter) = shebang_interpreter(&content) {
+                tools.push((interpreter, format!("{} (scripts/{})", by, file)));
+            }
+        }
+    }
+
+    tools
+}
+
+/// Words that start a requirement but never name a command
+const NOT_COMMANDS: &[&str] = &["a", "an", "the", "any", "access", "internet", "network", "connection"];
+
+/// Words allowed after a version number (e.g. "Python 3.10 or later")
+const VERSION_WORDS: &[&str] = &["or", "later", "newer", "higher", "above", "+"];
+
+/// Whether the text after a command is only a version (e.g. "18+", ">= 3.10")
+///
+/// A parenthesised note ("docker (for builds)") is ignored.
+fn is_version_suffix(rest: &str) -> bool {
+    let rest = rest.split('(').next().unwrap_or_default();
+    rest.split_whitespace().all(|word| {
+        word.starts_with(|c: char| c.is_ascii_digit() || "<>=~^".contains(c))
+            || VERSION_WORDS.contains(&word.to_lowercase().as_str())
+    })
+}
+
+/// Command names in a skill's `dependencies` (e.g. "ruff", "git, docker")
+///
+/// Version constraints such as "python>=3.10" or "Node 18+" are dropped,
+/// keeping only the command. Items that read as prose ("an internet
+/// connection", "access to GitHub") are skipped.
+pub fn parse_dependencies(text: &str) -> Vec<String> {
+    let mut tools: Vec<String> = Vec::new();
+    let items = text
+        .split([',', ';', '\n'])
+        .flat_map(|item| item.split(" and "))
+        .map(|item| item.trim().trim_start_matches("and ").trim());
+
+    for item in items {
+        let (word, rest) = item.split_once(char::is_whitespace).unwrap_or((item, ""));
+        if !is_version_suffix(rest) {
+            continue;
+        }
+        let word = word
+            .split(['<', '>', '=', '@', '~', '^', '!', '(', '['])
+            .next()
+            .unwrap_or_default()
+            .trim_matches(|c: char| !c.is_ascii_alphanumeric())
+            .to_lowercase();
+
+        let word = match word.as_str() {
+            "node.js" | "nodejs" => "node".to_string(),
+            _ => word,
+        };
+        let is_command = word.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && word.chars().all(|c| c.is_ascii_alphanumeric() || "._+-".contains(c))
+            && !NOT_COMMANDS.contains(&word.as_str());
+        if is_command && !tools.contains(&word) {
+            tools.push(word);
+        }
+    }
+
+    tools
+}
+
+/// Command names in a skill's `compatibility` (e.g. "Requires git, docker")
+///
+/// The field is free text, so only sentences starting with "Requires" or
+/// "Needs" are read.
+pub fn parse_compatibility(text: &str) -> Vec<String> {
+    let mut tools: Vec<String> = Vec::new();
+
+    for sentence in text.split(['.', '\n']) {
+        let sentence = sentence.trim();
+        let lower = sentence.to_lowercase();
+        let rest = ["requires", "needs"]
+            .iter()
+            .find(|word| lower.starts_with(*word))
+            .and_then(|word| sentence.get(word.len()..))
+            .map(|rest| rest.trim_start_matches(':'));
+
+        for tool in rest.map(parse_dependencies).unwrap_or_default() {
+            if !tools.contains(&tool) {
+                tools.push(tool);
+            }
+        }
+    }
+
+    tools
+}
+
+/// The interpreter a script's shebang runs (e.g. "python3" for
+/// `#!/usr/bin/env python3`)
+pub fn shebang_interpreter(script: &str) -> Option<String> {
+    let line = script.lines().next()?.strip_prefix("#!")?;
+    let mut words = line.split_whitespace();
+    let mut program = words.next()?;
+
+    // `env` looks the interpreter up on PATH; skip its flags and assignments
+    if file_name(program) == "env" {
+        program = words.find(|word| !word.starts_with('-') && !word.contains('='))?;
+    }
+
+    Some(file_name(program).to_string())
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path)
+}
+
 /// Get installation hints for common tools
 pub fn get_install_hint(tool: &str) -> Option<&'static str> {
     match tool {
         "docker" => Some("Install Docker: https://docs.docker.com/get-docker/"),
         "cargo" => Some("Install Rust: https://rustup.rs/"),
-        "npm" | "npx" => Some("Install Node.js: https://nodejs.org/"),
+        "npm" | "npx" | "node" => Some("Install Node.js: https://nodejs.org/"),
         "python" | "python3" => Some("Install Python: https://www.python.org/downloads/"),
         "go" => Some("Install Go: https://go.dev/dl/"),
-        "uv" => Some("Install uv: pip install uv"),
+        "uv" | "uvx" => Some("Install uv: pip install uv"),
+        "ruff" => Some("Install Ruff: pip install ruff"),
+        "git" => Some("Install Git: https://git-scm.com/downloads"),
+        "deno" => Some("Install Deno: https://deno.com/"),
+        "bun" | "bunx" => Some("Install Bun: https://bun.sh/"),
+        "gh" => Some("Install the GitHub CLI: https://cli.github.com/"),
+        "jq" => Some("Install jq: https://jqlang.github.io/jq/download/"),
         _ => None,
     }
 }
 
+/// What to tell the user about a missing tool
+pub fn remediation(tool: &str) -> String {
+    get_install_hint(tool)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("Install {} and make sure it is on PATH", tool))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,5 +223,71 @@ mod tests {
     fn test_install_hints() {
         assert!(get_install_hint("docker").is_some());
         assert!(get_install_hint("unknown-tool").is_none());
+        assert_eq!(remediation("zig"), "Install zig and make sure it is on PATH");
+    }
+
+    #[test]
+    fn test_parse_dependencies() {
+        assert_eq!(parse_dependencies("ruff"), ["ruff"]);
+        assert_eq!(parse_dependencies("git, docker and jq"), ["git", "docker", "jq"]);
+        assert_eq!(parse_dependencies("python>=3.10, Node.js 18+"), ["python", "node"]);
+
+        assert_eq!(parse_compatibility("Requires git, docker"), ["git", "docker"]);
+        assert_eq!(
+            parse_compatibility("Designed for Claude Code. Needs: uv and ruff."),
+            ["uv", "ruff"]
+        );
+        assert!(parse_compatibility("Works offline, any OS").is_empty());
+    }
+
+    #[test]
+    fn test_parse_prose_requirements() {
+        assert!(parse_compatibility("Requires an internet connection").is_empty());
+        assert!(parse_compatibility("Requires access to GitHub").is_empty());
+        assert!(parse_compatibility("Needs internet access.").is_empty());
+        assert_eq!(parse_compatibility("Requires git and an internet connection"), ["git"]);
+        assert_eq!(parse_compatibility("Requires Python 3.10 or later"), ["python"]);
+        assert_eq!(parse_dependencies("docker (for builds), node >= 18"), ["docker", "node"]);
+    }
+
+    #[test]
+    fn test_shebang_interpreter() {
+        assert_eq!(shebang_interpreter("#!/usr/bin/env python3\nprint()").as_deref(), Some("python3"));
+        assert_eq!(shebang_interpreter("#!/usr/bin/env -S uv run --script").as_deref(), Some("uv"));
+        assert_eq!(shebang_interpreter("#! /bin/bash -e").as_deref(), Some("bash"));
+        assert_eq!(shebang_interpreter("print('no shebang')"), None);
+    }
+
+    #[test]
+    fn test_required_tools() {
+        let yaml = r#"
+name: demo
+version: 1.0.0
+description: d
+author: a
+identity:
+  system_prompt: p
+skills:
+  - name: lint
+    content: c
+    dependencies: ruff
+    compatibility: Requires git
+mcp:
+  - name: search
+    command: npx
+"#;
+        let agent: AgentConfig = serde_yaml::from_str(yaml).unwrap();
+        let scripts = |_: &Skill| vec![("run.py".to_string(), "#!/usr/bin/env python3\n".to_string())];
+
+        let tools = required_tools(&agent, scripts);
+        assert_eq!(
+            tools,
+            [
+                ("npx".to_string(), "MCP tool 'search'".to_string()),
+                ("ruff".to_string(), "skill 'lint'".to_string()),
+                ("git".to_string(), "skill 'lint'".to_string()),
+                ("python3".to_string(), "skill 'lint' (scripts/run.py)".to_string()),
+            ]
+        );
     }
 }